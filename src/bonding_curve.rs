@@ -0,0 +1,770 @@
+use scrypto::prelude::*;
+
+// trait implemented by every bonding curve that can be used by a RadixMemeTokenCurve component.
+// All functions are pure functions that only depend on the curve parameters and the values passed in,
+// which allows new curve shapes to be added without touching the trade logic in the component.
+pub trait BondingCurve {
+    // function to calculate the current price on the bonding curve based on the current token supply
+    fn calculate_price(&self, supply: Decimal) -> Decimal;
+
+    // function to calculate the buy price (XRD required) in order to receive a specified amount of new tokens
+    fn calculate_buy_price(&self, new_tokens: Decimal, supply: Decimal) -> Decimal;
+
+    // function to calculate how many tokens can be bought with the specified amount of XRD
+    fn calculate_tokens_received(&self, xrd_received: Decimal, supply: Decimal) -> Decimal;
+
+    // function to calculate the sell price (XRD received) from selling the specified number of tokens
+    fn calculate_sell_price(&self, sell_tokens: Decimal, supply: Decimal) -> Decimal;
+
+    // function to calculate the amount of tokens to sell to receive the specified amount of XRD
+    fn calculate_tokens_to_sell(&self, xrd_required: Decimal, supply: Decimal) -> Decimal;
+
+    // function to calculate the total XRD that will be received when the max supply has been traded on the curve
+    fn calculate_max_xrd(&self, max_token_supply_to_trade: Decimal) -> Decimal;
+}
+
+// the type of bonding curve (and any creator chosen parameters) to use when creating a new token curve component.
+// The remaining curve parameters are solved from the max_xrd_market_cap and max_token_supply_to_trade values.
+#[derive(ScryptoSbor, ManifestSbor, Clone, Debug, PartialEq)]
+pub enum BondingCurveConfig {
+    // price = initial_price + m * supply^exponent, e.g. exponent 2 for a quadratic curve
+    Power {
+        exponent: Decimal,
+        initial_price: Decimal,
+    },
+    // price = initial_price + b * supply
+    Linear {
+        initial_price: Decimal,
+    },
+    // price = xrd_reserve / token_reserve with xrd_reserve * token_reserve constant.
+    // The virtual xrd reserve is solved so that the price at the max supply matches the max market cap.
+    VirtualReserve {
+        virtual_token_reserve: Decimal,
+    },
+}
+
+// the kind of bonding curve used by a token curve component, together with its parameters.
+// This is stored on the component and all curve calcs are dispatched through it.
+#[derive(ScryptoSbor, Clone, Debug)]
+pub enum BondingCurveKind {
+    Power(PowerCurve),
+    Linear(LinearCurve),
+    VirtualReserve(VirtualReserveCurve),
+}
+
+impl BondingCurveKind {
+    // creates the bonding curve specified in the config, solved to reach the max_xrd_market_cap at the max_token_supply_to_trade
+    pub fn new(
+        config: BondingCurveConfig,
+        max_xrd_market_cap: Decimal,
+        max_token_supply_to_trade: Decimal,
+    ) -> Self {
+        match config {
+            BondingCurveConfig::Power {
+                exponent,
+                initial_price,
+            } => BondingCurveKind::Power(PowerCurve::new(
+                exponent,
+                initial_price,
+                max_xrd_market_cap,
+                max_token_supply_to_trade,
+            )),
+            BondingCurveConfig::Linear { initial_price } => BondingCurveKind::Linear(
+                LinearCurve::new(initial_price, max_xrd_market_cap, max_token_supply_to_trade),
+            ),
+            BondingCurveConfig::VirtualReserve {
+                virtual_token_reserve,
+            } => BondingCurveKind::VirtualReserve(VirtualReserveCurve::new(
+                virtual_token_reserve,
+                max_xrd_market_cap,
+                max_token_supply_to_trade,
+            )),
+        }
+    }
+}
+
+impl BondingCurve for BondingCurveKind {
+    fn calculate_price(&self, supply: Decimal) -> Decimal {
+        match self {
+            BondingCurveKind::Power(curve) => curve.calculate_price(supply),
+            BondingCurveKind::Linear(curve) => curve.calculate_price(supply),
+            BondingCurveKind::VirtualReserve(curve) => curve.calculate_price(supply),
+        }
+    }
+
+    fn calculate_buy_price(&self, new_tokens: Decimal, supply: Decimal) -> Decimal {
+        match self {
+            BondingCurveKind::Power(curve) => curve.calculate_buy_price(new_tokens, supply),
+            BondingCurveKind::Linear(curve) => curve.calculate_buy_price(new_tokens, supply),
+            BondingCurveKind::VirtualReserve(curve) => {
+                curve.calculate_buy_price(new_tokens, supply)
+            }
+        }
+    }
+
+    fn calculate_tokens_received(&self, xrd_received: Decimal, supply: Decimal) -> Decimal {
+        match self {
+            BondingCurveKind::Power(curve) => curve.calculate_tokens_received(xrd_received, supply),
+            BondingCurveKind::Linear(curve) => {
+                curve.calculate_tokens_received(xrd_received, supply)
+            }
+            BondingCurveKind::VirtualReserve(curve) => {
+                curve.calculate_tokens_received(xrd_received, supply)
+            }
+        }
+    }
+
+    fn calculate_sell_price(&self, sell_tokens: Decimal, supply: Decimal) -> Decimal {
+        match self {
+            BondingCurveKind::Power(curve) => curve.calculate_sell_price(sell_tokens, supply),
+            BondingCurveKind::Linear(curve) => curve.calculate_sell_price(sell_tokens, supply),
+            BondingCurveKind::VirtualReserve(curve) => {
+                curve.calculate_sell_price(sell_tokens, supply)
+            }
+        }
+    }
+
+    fn calculate_tokens_to_sell(&self, xrd_required: Decimal, supply: Decimal) -> Decimal {
+        match self {
+            BondingCurveKind::Power(curve) => curve.calculate_tokens_to_sell(xrd_required, supply),
+            BondingCurveKind::Linear(curve) => curve.calculate_tokens_to_sell(xrd_required, supply),
+            BondingCurveKind::VirtualReserve(curve) => {
+                curve.calculate_tokens_to_sell(xrd_required, supply)
+            }
+        }
+    }
+
+    fn calculate_max_xrd(&self, max_token_supply_to_trade: Decimal) -> Decimal {
+        match self {
+            BondingCurveKind::Power(curve) => curve.calculate_max_xrd(max_token_supply_to_trade),
+            BondingCurveKind::Linear(curve) => curve.calculate_max_xrd(max_token_supply_to_trade),
+            BondingCurveKind::VirtualReserve(curve) => {
+                curve.calculate_max_xrd(max_token_supply_to_trade)
+            }
+        }
+    }
+}
+
+// bonding curve where price = initial_price + multiplier * (supply / max_token_supply_to_trade)^exponent
+// the exponent can be any positive value with at most one decimal place (e.g. 1.5, 2 or 3), up to MAX_POWER_CURVE_EXPONENT.
+// The supply is normalised to a value between 0 and 1 before any powers are taken, so that supply^(exponent + 1)
+// cannot overflow a PreciseDecimal for large supplies.
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct PowerCurve {
+    pub exponent: Decimal, // the exponent of the curve as chosen by the creator, e.g. 2 for a quadratic curve
+    pub exponent_numerator: i64, // the exponent written as a reduced fraction, used for the integer powers and roots in the curve calcs
+    pub exponent_denominator: i64,
+    pub initial_price: Decimal, // the price of the first token bought on the curve. A non-zero value stops the first buyers from getting tokens for (almost) nothing.
+    pub multiplier: PreciseDecimal, // the constant multiplier that is used in the bonding curve calcs. This is based on the max_supply, max_xrd and initial_price values.
+    pub max_token_supply_to_trade: Decimal, // the supply used to normalise the supply in the curve calcs
+}
+
+pub const MAX_POWER_CURVE_EXPONENT: Decimal = dec!("5");
+const MAX_SOLVER_ITERATIONS: u32 = 100;
+const SOLVER_XRD_TOLERANCE: Decimal = dec!("0.000000000001"); // the max difference in XRD between the solved and the target integral
+
+impl PowerCurve {
+    // creates a new power curve that starts at the initial_price and reaches the max_xrd_market_cap when the max_token_supply_to_trade has been bought
+    pub fn new(
+        exponent: Decimal,
+        initial_price: Decimal,
+        max_xrd_market_cap: Decimal,
+        max_token_supply_to_trade: Decimal,
+    ) -> Self {
+        assert!(
+            exponent > Decimal::ZERO && exponent <= MAX_POWER_CURVE_EXPONENT,
+            "Bonding curve exponent must be larger than 0 and not more than {}.",
+            MAX_POWER_CURVE_EXPONENT
+        );
+        let exponent_tenths = (1..=50)
+            .find(|tenths| Decimal::from(*tenths) == exponent * Decimal::from(10))
+            .expect("Bonding curve exponent can have at most one decimal place.");
+        let divisor = greatest_common_divisor(exponent_tenths, 10);
+        let mut new_curve = PowerCurve {
+            exponent,
+            exponent_numerator: exponent_tenths / divisor,
+            exponent_denominator: 10 / divisor,
+            initial_price,
+            multiplier: PreciseDecimal::ZERO,
+            max_token_supply_to_trade,
+        };
+        new_curve.multiplier =
+            new_curve.calculate_multiplier(max_xrd_market_cap, max_token_supply_to_trade);
+        new_curve
+    }
+
+    // the price at the max supply must be max_xrd_market_cap / max_supply = initial_price + multiplier * 1^exponent,
+    // so multiplier = market_cap / max_supply - initial_price
+    fn calculate_multiplier(
+        &self,
+        max_xrd_market_cap: Decimal,
+        max_token_supply_to_trade: Decimal,
+    ) -> PreciseDecimal {
+        assert!(
+            max_token_supply_to_trade > Decimal::ZERO,
+            "max_token_supply_to_trade must be positive."
+        );
+        let end_price = PreciseDecimal::from(max_xrd_market_cap)
+            .checked_div(max_token_supply_to_trade)
+            .expect("Problem in calculating multiplier. First div");
+        assert!(
+            self.initial_price >= Decimal::ZERO
+                && PreciseDecimal::from(self.initial_price) < end_price,
+            "initial_price must not be negative and must be less than the price at the max market cap."
+        );
+        end_price - self.initial_price
+    }
+
+    // calculates supply / max_token_supply_to_trade, the supply as a fraction of the max supply
+    fn normalise(&self, supply: PreciseDecimal) -> PreciseDecimal {
+        supply
+            .checked_div(self.max_token_supply_to_trade)
+            .expect("normalise problem. First div")
+    }
+
+    // calculates normalised_supply * max_token_supply_to_trade, the inverse of normalise
+    fn denormalise(&self, normalised_supply: PreciseDecimal) -> PreciseDecimal {
+        normalised_supply
+            .checked_mul(self.max_token_supply_to_trade)
+            .expect("denormalise problem. First mul")
+    }
+
+    // the numerator of exponent + 1, which is the exponent of the integral of the curve
+    fn integral_numerator(&self) -> i64 {
+        self.exponent_numerator + self.exponent_denominator
+    }
+
+    // calculates value^exponent
+    fn power(&self, value: PreciseDecimal) -> PreciseDecimal {
+        precise_pow(value, self.exponent_numerator, self.exponent_denominator)
+    }
+
+    // calculates value^(exponent + 1)
+    fn integral_power(&self, value: PreciseDecimal) -> PreciseDecimal {
+        precise_pow(value, self.integral_numerator(), self.exponent_denominator)
+    }
+
+    // calculates value^(1 / (exponent + 1)), the inverse of integral_power
+    fn inverse_integral_power(&self, value: PreciseDecimal) -> PreciseDecimal {
+        precise_pow(value, self.exponent_denominator, self.integral_numerator())
+    }
+
+    // calculates multiplier * max_supply / (exponent + 1), the constant in front of the integral of the curve
+    fn integral_multiplier(&self) -> PreciseDecimal {
+        self.multiplier
+            .checked_mul(self.max_token_supply_to_trade)
+            .expect("integral_multiplier problem. Supply mul")
+            .checked_mul(self.exponent_denominator)
+            .expect("integral_multiplier problem. First mul")
+            .checked_div(self.integral_numerator())
+            .expect("integral_multiplier problem. First div")
+    }
+
+    // calculates xrd * (exponent + 1) / (multiplier * max_supply), the xrd amount expressed as a change in normalised_supply^(exponent + 1)
+    fn xrd_to_integral_power(&self, xrd: PreciseDecimal) -> PreciseDecimal {
+        xrd.checked_div(self.multiplier.clone())
+            .expect("xrd_to_integral_power problem. First div")
+            .checked_div(self.max_token_supply_to_trade)
+            .expect("xrd_to_integral_power problem. Supply div")
+            .checked_mul(self.integral_numerator())
+            .expect("xrd_to_integral_power problem. First mul")
+            .checked_div(self.exponent_denominator)
+            .expect("xrd_to_integral_power problem. Second div")
+    }
+
+    // the price on the curve at the specified supply, at full precision
+    fn precise_price(&self, supply: PreciseDecimal) -> PreciseDecimal {
+        self.multiplier.clone() * self.power(self.normalise(supply)) + self.initial_price
+    }
+
+    // the area under the curve from 0 to supply, i.e. the xrd needed to buy supply tokens from an empty curve
+    fn integral(&self, supply: PreciseDecimal) -> PreciseDecimal {
+        PreciseDecimal::from(self.initial_price)
+            .checked_mul(supply)
+            .expect("integral problem. First mul")
+            + self
+                .integral_multiplier()
+                .checked_mul(self.integral_power(self.normalise(supply)))
+                .expect("integral problem. Second mul")
+    }
+
+    // finds the supply where the integral of the curve equals target_integral, using newton's method.
+    // The integral is convex and increasing, so starting from a supply at or above the solution converges from above without overshooting.
+    fn solve_supply(
+        &self,
+        target_integral: PreciseDecimal,
+        start_supply: PreciseDecimal,
+    ) -> PreciseDecimal {
+        let precision = PreciseDecimal::from(dec!("0.000000000000000001"));
+        let mut supply = start_supply;
+        for _ in 0..MAX_SOLVER_ITERATIONS {
+            let step = (self.integral(supply) - target_integral)
+                .checked_div(self.precise_price(supply))
+                .expect("solve_supply problem. First div");
+            supply = supply - step;
+            if step.checked_abs().expect("solve_supply problem. Abs") < precision {
+                break;
+            }
+        }
+        let residual = (self.integral(supply) - target_integral)
+            .checked_abs()
+            .expect("solve_supply problem. Residual abs");
+        assert!(
+            residual < PreciseDecimal::from(SOLVER_XRD_TOLERANCE),
+            "solve_supply problem. Supply did not converge. Residual: {}",
+            residual
+        );
+        supply
+    }
+}
+
+impl BondingCurve for PowerCurve {
+    fn calculate_price(&self, supply: Decimal) -> Decimal {
+        Decimal::try_from(self.precise_price(PreciseDecimal::from(supply)))
+            .expect("calculate_price problem. Cant convert precise decimal to decimal.")
+    }
+
+    fn calculate_buy_price(&self, new_tokens: Decimal, supply: Decimal) -> Decimal {
+        let mut result = Decimal::ZERO;
+        if new_tokens > Decimal::ZERO {
+            let precise_supply = PreciseDecimal::from(supply);
+            let first_value = self.integral_multiplier();
+            let second_value = self.integral_power(self.normalise(precise_supply + new_tokens));
+            let third_value = self.integral_power(self.normalise(precise_supply));
+            let fourth_value = second_value - third_value;
+            let precise_price = first_value
+                .checked_mul(fourth_value)
+                .expect("calculate_buy_price problem. Final Multiply.")
+                + PreciseDecimal::from(self.initial_price)
+                    .checked_mul(new_tokens)
+                    .expect("calculate_buy_price problem. Initial price multiply.");
+            result = round_to_decimal(precise_price, "calculate_buy_price")
+        }
+        result
+    }
+
+    // without an initial price the curve can be inverted directly. With an initial price the supply is solved numerically,
+    // starting from the closed form result without the initial price (which always buys at least as many tokens).
+    fn calculate_tokens_received(&self, xrd_received: Decimal, supply: Decimal) -> Decimal {
+        let mut result = Decimal::ZERO;
+        if xrd_received > Decimal::ZERO {
+            let precise_supply = PreciseDecimal::from(supply);
+            let first_value = self.xrd_to_integral_power(PreciseDecimal::from(xrd_received));
+            let second_value = self.integral_power(self.normalise(precise_supply));
+            let mut third_value =
+                self.denormalise(self.inverse_integral_power(first_value + second_value));
+            if self.initial_price > Decimal::ZERO {
+                let max_supply_at_initial_price = precise_supply
+                    + PreciseDecimal::from(xrd_received)
+                        .checked_div(self.initial_price)
+                        .expect("calculate_tokens_received problem. Initial price div");
+                if max_supply_at_initial_price < third_value {
+                    third_value = max_supply_at_initial_price;
+                }
+                third_value =
+                    self.solve_supply(self.integral(precise_supply) + xrd_received, third_value);
+            }
+            let precise_result = third_value - precise_supply;
+            result = round_to_decimal(precise_result, "calculate_tokens_received");
+        }
+        result
+    }
+
+    fn calculate_sell_price(&self, sell_tokens: Decimal, supply: Decimal) -> Decimal {
+        let mut result = Decimal::ZERO;
+        if sell_tokens > Decimal::ZERO {
+            let precise_supply = PreciseDecimal::from(supply);
+            let precise_new_supply = precise_supply.clone() - sell_tokens;
+
+            let first_value = self.integral_multiplier();
+            let second_value = self.integral_power(self.normalise(precise_supply));
+            let third_value = self.integral_power(self.normalise(precise_new_supply));
+            let fourth_value = second_value - third_value;
+
+            let precise_price = first_value
+                .checked_mul(fourth_value)
+                .expect("calculate_sell_price problem. Multiplication problem.")
+                + PreciseDecimal::from(self.initial_price)
+                    .checked_mul(sell_tokens)
+                    .expect("calculate_sell_price problem. Initial price multiply.");
+            result = round_to_decimal(precise_price, "calculate_sell_price");
+        }
+        result
+    }
+
+    // without an initial price the curve can be inverted directly. With an initial price the new supply is solved numerically,
+    // starting from the current supply.
+    fn calculate_tokens_to_sell(&self, xrd_required: Decimal, supply: Decimal) -> Decimal {
+        let mut result = Decimal::ZERO;
+        if xrd_required > Decimal::ZERO {
+            let precise_supply = PreciseDecimal::from(supply);
+            let fourth_value = if self.initial_price > Decimal::ZERO {
+                self.solve_supply(self.integral(precise_supply) - xrd_required, precise_supply)
+            } else {
+                let first_value = self.xrd_to_integral_power(PreciseDecimal::from(xrd_required));
+                let second_value = self.integral_power(self.normalise(precise_supply));
+                let third_value = second_value - first_value;
+                self.denormalise(self.inverse_integral_power(third_value))
+            };
+            let precise_result = precise_supply - fourth_value;
+            result = round_to_decimal(precise_result, "calculate_tokens_to_sell");
+        }
+        result
+    }
+
+    fn calculate_max_xrd(&self, max_token_supply_to_trade: Decimal) -> Decimal {
+        let first_value = self.integral_multiplier();
+        let second_value =
+            self.integral_power(self.normalise(PreciseDecimal::from(max_token_supply_to_trade)));
+        let precise_max_xrd: PreciseDecimal = first_value
+            .checked_mul(second_value)
+            .expect("Problem calculating max_xrd. First mul")
+            + PreciseDecimal::from(self.initial_price)
+                .checked_mul(max_token_supply_to_trade)
+                .expect("Problem calculating max_xrd. Initial price mul");
+        Decimal::try_from(precise_max_xrd)
+            .expect("Problem calculating max_xrd. Could not convert precise_max_xrd to decimal")
+    }
+}
+
+// rounds a PreciseDecimal curve result to a Decimal, rounding to 18 decimal places with midpoints rounded away from zero.
+// All curve calculations round through this function so that results calculated off ledger match the component exactly.
+pub fn round_to_decimal(value: PreciseDecimal, calculation: &str) -> Decimal {
+    let rounded = value
+        .checked_round(18, RoundingMode::ToNearestMidpointAwayFromZero)
+        .unwrap_or_else(|| panic!("{} problem. Cant round precise decimal.", calculation));
+    Decimal::try_from(rounded).unwrap_or_else(|_| {
+        panic!(
+            "{} problem. Cant convert precise decimal to decimal.",
+            calculation
+        )
+    })
+}
+
+// calculates value^(numerator / denominator). Integer powers are calculated directly so that they are exact.
+fn precise_pow(value: PreciseDecimal, numerator: i64, denominator: i64) -> PreciseDecimal {
+    let root = if denominator == 1 {
+        value
+    } else {
+        value
+            .checked_nth_root(denominator as u32)
+            .expect("precise_pow problem. Root")
+    };
+    if numerator == 1 {
+        root
+    } else {
+        root.checked_powi(numerator)
+            .expect("precise_pow problem. Powi")
+    }
+}
+
+fn greatest_common_divisor(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        greatest_common_divisor(b, a % b)
+    }
+}
+
+// bonding curve where price = initial_price + slope * supply
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct LinearCurve {
+    pub initial_price: Decimal, // the price of the first token bought on the curve
+    pub slope: PreciseDecimal, // the increase in price per token. This is based on the initial_price, max_supply and max_xrd values.
+}
+
+impl LinearCurve {
+    // creates a new linear curve that starts at the initial_price and reaches the max_xrd_market_cap when the max_token_supply_to_trade has been bought
+    pub fn new(
+        initial_price: Decimal,
+        max_xrd_market_cap: Decimal,
+        max_token_supply_to_trade: Decimal,
+    ) -> Self {
+        let precise_max_supply = PreciseDecimal::from(max_token_supply_to_trade);
+        let end_price = PreciseDecimal::from(max_xrd_market_cap)
+            .checked_div(precise_max_supply)
+            .expect("Problem in calculating slope. First div");
+        assert!(
+            initial_price >= Decimal::ZERO && PreciseDecimal::from(initial_price) < end_price,
+            "initial_price must be positive and less than the price at the max market cap."
+        );
+        let slope = (end_price - initial_price)
+            .checked_div(precise_max_supply)
+            .expect("Problem in calculating slope. Second div");
+        LinearCurve {
+            initial_price,
+            slope,
+        }
+    }
+
+    // the price on the curve at the specified supply, at full precision
+    fn precise_price(&self, supply: PreciseDecimal) -> PreciseDecimal {
+        self.slope
+            .checked_mul(supply)
+            .expect("precise_price problem. First mul")
+            + self.initial_price
+    }
+
+    // the area under the curve from supply to supply + tokens, i.e. initial_price * tokens + slope / 2 * ((supply + tokens)^2 - supply^2)
+    fn precise_area(&self, tokens: PreciseDecimal, supply: PreciseDecimal) -> PreciseDecimal {
+        let first_value = PreciseDecimal::from(self.initial_price)
+            .checked_mul(tokens)
+            .expect("precise_area problem. First mul");
+        let second_value = (supply + tokens)
+            .checked_powi(2)
+            .expect("precise_area problem. First powi")
+            - supply
+                .checked_powi(2)
+                .expect("precise_area problem. Second powi");
+        let third_value = self
+            .slope
+            .checked_div(2)
+            .expect("precise_area problem. First div")
+            .checked_mul(second_value)
+            .expect("precise_area problem. Second mul");
+        first_value + third_value
+    }
+}
+
+impl BondingCurve for LinearCurve {
+    fn calculate_price(&self, supply: Decimal) -> Decimal {
+        Decimal::try_from(self.precise_price(PreciseDecimal::from(supply)))
+            .expect("calculate_price problem. Cant convert precise decimal to decimal.")
+    }
+
+    fn calculate_buy_price(&self, new_tokens: Decimal, supply: Decimal) -> Decimal {
+        let mut result = Decimal::ZERO;
+        if new_tokens > Decimal::ZERO {
+            let precise_price = self.precise_area(
+                PreciseDecimal::from(new_tokens),
+                PreciseDecimal::from(supply),
+            );
+            result = round_to_decimal(precise_price, "calculate_buy_price")
+        }
+        result
+    }
+
+    // solves initial_price * tokens + slope / 2 * ((supply + tokens)^2 - supply^2) = xrd_received for tokens
+    // using the form tokens = 2 * xrd / (price + sqrt(price^2 + 2 * slope * xrd)), which is also valid for a zero slope
+    fn calculate_tokens_received(&self, xrd_received: Decimal, supply: Decimal) -> Decimal {
+        let mut result = Decimal::ZERO;
+        if xrd_received > Decimal::ZERO {
+            let precise_xrd_received = PreciseDecimal::from(xrd_received);
+            let current_price = self.precise_price(PreciseDecimal::from(supply));
+            let first_value = current_price
+                .checked_powi(2)
+                .expect("calculate_tokens_received problem. First powi");
+            let second_value = self
+                .slope
+                .checked_mul(precise_xrd_received)
+                .expect("calculate_tokens_received problem. First mul")
+                .checked_mul(2)
+                .expect("calculate_tokens_received problem. Second mul");
+            let third_value = (first_value + second_value)
+                .checked_nth_root(2)
+                .expect("calculate_tokens_received problem. First root");
+            let precise_result = precise_xrd_received
+                .checked_mul(2)
+                .expect("calculate_tokens_received problem. Third mul")
+                .checked_div(current_price + third_value)
+                .expect("calculate_tokens_received problem. First div");
+            result = round_to_decimal(precise_result, "calculate_tokens_received");
+        }
+        result
+    }
+
+    fn calculate_sell_price(&self, sell_tokens: Decimal, supply: Decimal) -> Decimal {
+        let mut result = Decimal::ZERO;
+        if sell_tokens > Decimal::ZERO {
+            let precise_sell_tokens = PreciseDecimal::from(sell_tokens);
+            let precise_new_supply = PreciseDecimal::from(supply) - precise_sell_tokens;
+            let precise_price = self.precise_area(precise_sell_tokens, precise_new_supply);
+            result = round_to_decimal(precise_price, "calculate_sell_price");
+        }
+        result
+    }
+
+    // solves initial_price * tokens + slope / 2 * (supply^2 - (supply - tokens)^2) = xrd_required for tokens
+    // using the form tokens = 2 * xrd / (price + sqrt(price^2 - 2 * slope * xrd))
+    fn calculate_tokens_to_sell(&self, xrd_required: Decimal, supply: Decimal) -> Decimal {
+        let mut result = Decimal::ZERO;
+        if xrd_required > Decimal::ZERO {
+            let precise_xrd_required = PreciseDecimal::from(xrd_required);
+            let current_price = self.precise_price(PreciseDecimal::from(supply));
+            let first_value = current_price
+                .checked_powi(2)
+                .expect("calculate_tokens_to_sell problem. First powi");
+            let second_value = self
+                .slope
+                .checked_mul(precise_xrd_required)
+                .expect("calculate_tokens_to_sell problem. First mul")
+                .checked_mul(2)
+                .expect("calculate_tokens_to_sell problem. Second mul");
+            let third_value = (first_value - second_value)
+                .checked_nth_root(2)
+                .expect("calculate_tokens_to_sell problem. First root");
+            let precise_result = precise_xrd_required
+                .checked_mul(2)
+                .expect("calculate_tokens_to_sell problem. Third mul")
+                .checked_div(current_price + third_value)
+                .expect("calculate_tokens_to_sell problem. First div");
+            result = round_to_decimal(precise_result, "calculate_tokens_to_sell");
+        }
+        result
+    }
+
+    fn calculate_max_xrd(&self, max_token_supply_to_trade: Decimal) -> Decimal {
+        let precise_max_xrd = self.precise_area(
+            PreciseDecimal::from(max_token_supply_to_trade),
+            PreciseDecimal::ZERO,
+        );
+        Decimal::try_from(precise_max_xrd)
+            .expect("Problem calculating max_xrd. Could not convert precise_max_xrd to decimal")
+    }
+}
+
+// constant product bonding curve using virtual reserves, where xrd_reserve * token_reserve = constant_product.
+// The curve starts with the virtual reserves, so the starting price is virtual_xrd_reserve / virtual_token_reserve.
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct VirtualReserveCurve {
+    pub virtual_xrd_reserve: Decimal, // the xrd reserve of the curve when no tokens have been bought. This is based on the max_xrd_market_cap
+    pub virtual_token_reserve: Decimal, // the token reserve of the curve when no tokens have been bought
+    pub constant_product: PreciseDecimal, // virtual_xrd_reserve * virtual_token_reserve
+}
+
+impl VirtualReserveCurve {
+    // creates a new virtual reserve curve that reaches the max_xrd_market_cap when the max_token_supply_to_trade has been bought.
+    // The virtual token reserve must be larger than the max_token_supply_to_trade, otherwise the price would go to infinity before all tokens have been bought.
+    pub fn new(
+        virtual_token_reserve: Decimal,
+        max_xrd_market_cap: Decimal,
+        max_token_supply_to_trade: Decimal,
+    ) -> Self {
+        assert!(
+            max_token_supply_to_trade > Decimal::ZERO && max_xrd_market_cap > Decimal::ZERO,
+            "max_token_supply_to_trade and max_xrd_market_cap must be positive."
+        );
+        assert!(
+            virtual_token_reserve > max_token_supply_to_trade,
+            "virtual_token_reserve must be larger than max_token_supply_to_trade."
+        );
+        let virtual_xrd_reserve = Self::calculate_virtual_xrd_reserve(
+            virtual_token_reserve,
+            max_xrd_market_cap,
+            max_token_supply_to_trade,
+        );
+        assert!(
+            virtual_xrd_reserve > Decimal::ZERO,
+            "virtual_xrd_reserve must be larger than 0. Increase max_xrd_market_cap or reduce virtual_token_reserve."
+        );
+        VirtualReserveCurve {
+            virtual_xrd_reserve,
+            virtual_token_reserve,
+            constant_product: PreciseDecimal::from(virtual_xrd_reserve)
+                .checked_mul(virtual_token_reserve)
+                .expect("Problem in calculating constant product. First mul"),
+        }
+    }
+
+    // the price at the max supply must be max_xrd_market_cap / max_supply = xrd_reserve * virtual_token_reserve / (virtual_token_reserve - max_supply)^2,
+    // so virtual_xrd_reserve = market_cap * (virtual_token_reserve - max_supply)^2 / (max_supply * virtual_token_reserve)
+    fn calculate_virtual_xrd_reserve(
+        virtual_token_reserve: Decimal,
+        max_xrd_market_cap: Decimal,
+        max_token_supply_to_trade: Decimal,
+    ) -> Decimal {
+        let end_token_reserve =
+            PreciseDecimal::from(virtual_token_reserve) - max_token_supply_to_trade;
+        let virtual_xrd_reserve = PreciseDecimal::from(max_xrd_market_cap)
+            .checked_div(max_token_supply_to_trade)
+            .expect("Problem in calculating virtual_xrd_reserve. First div")
+            .checked_mul(end_token_reserve)
+            .expect("Problem in calculating virtual_xrd_reserve. First mul")
+            .checked_div(virtual_token_reserve)
+            .expect("Problem in calculating virtual_xrd_reserve. Second div")
+            .checked_mul(end_token_reserve)
+            .expect("Problem in calculating virtual_xrd_reserve. Second mul");
+        round_to_decimal(virtual_xrd_reserve, "calculate_virtual_xrd_reserve")
+    }
+
+    // the token reserve remaining on the curve at the specified supply
+    fn token_reserve(&self, supply: PreciseDecimal) -> PreciseDecimal {
+        PreciseDecimal::from(self.virtual_token_reserve) - supply
+    }
+
+    // the xrd reserve on the curve at the specified supply
+    fn xrd_reserve(&self, supply: PreciseDecimal) -> PreciseDecimal {
+        self.constant_product
+            .checked_div(self.token_reserve(supply))
+            .expect("xrd_reserve problem. First div")
+    }
+}
+
+impl BondingCurve for VirtualReserveCurve {
+    fn calculate_price(&self, supply: Decimal) -> Decimal {
+        let precise_supply = PreciseDecimal::from(supply);
+        Decimal::try_from(
+            self.xrd_reserve(precise_supply)
+                .checked_div(self.token_reserve(precise_supply))
+                .expect("calculate_price problem. First div"),
+        )
+        .expect("calculate_price problem. Cant convert precise decimal to decimal.")
+    }
+
+    fn calculate_buy_price(&self, new_tokens: Decimal, supply: Decimal) -> Decimal {
+        let mut result = Decimal::ZERO;
+        if new_tokens > Decimal::ZERO {
+            let precise_supply = PreciseDecimal::from(supply);
+            let precise_price =
+                self.xrd_reserve(precise_supply + new_tokens) - self.xrd_reserve(precise_supply);
+            result = round_to_decimal(precise_price, "calculate_buy_price")
+        }
+        result
+    }
+
+    fn calculate_tokens_received(&self, xrd_received: Decimal, supply: Decimal) -> Decimal {
+        let mut result = Decimal::ZERO;
+        if xrd_received > Decimal::ZERO {
+            let precise_supply = PreciseDecimal::from(supply);
+            let new_token_reserve = self
+                .constant_product
+                .checked_div(self.xrd_reserve(precise_supply) + xrd_received)
+                .expect("calculate_tokens_received problem. First div");
+            let precise_result = self.token_reserve(precise_supply) - new_token_reserve;
+            result = round_to_decimal(precise_result, "calculate_tokens_received");
+        }
+        result
+    }
+
+    fn calculate_sell_price(&self, sell_tokens: Decimal, supply: Decimal) -> Decimal {
+        let mut result = Decimal::ZERO;
+        if sell_tokens > Decimal::ZERO {
+            let precise_supply = PreciseDecimal::from(supply);
+            let precise_price =
+                self.xrd_reserve(precise_supply) - self.xrd_reserve(precise_supply - sell_tokens);
+            result = round_to_decimal(precise_price, "calculate_sell_price");
+        }
+        result
+    }
+
+    fn calculate_tokens_to_sell(&self, xrd_required: Decimal, supply: Decimal) -> Decimal {
+        let mut result = Decimal::ZERO;
+        if xrd_required > Decimal::ZERO {
+            let precise_supply = PreciseDecimal::from(supply);
+            let new_token_reserve = self
+                .constant_product
+                .checked_div(self.xrd_reserve(precise_supply) - xrd_required)
+                .expect("calculate_tokens_to_sell problem. First div");
+            let precise_result = new_token_reserve - self.token_reserve(precise_supply);
+            result = round_to_decimal(precise_result, "calculate_tokens_to_sell");
+        }
+        result
+    }
+
+    fn calculate_max_xrd(&self, max_token_supply_to_trade: Decimal) -> Decimal {
+        let precise_max_xrd = self.xrd_reserve(PreciseDecimal::from(max_token_supply_to_trade))
+            - self.virtual_xrd_reserve;
+        Decimal::try_from(precise_max_xrd)
+            .expect("Problem calculating max_xrd. Could not convert precise_max_xrd to decimal")
+    }
+}
@@ -0,0 +1,270 @@
+use crate::bonding_curve::{BondingCurve, BondingCurveKind};
+use scrypto::prelude::*;
+
+// the trade calculations used by the RadixMemeTokenCurve component.
+// These functions make no runtime calls and only depend on the curve and the state passed in, so they can be used
+// off ledger (e.g. by indexers, bots and tests) and will return exactly the same results as the component.
+
+// the result of a trade on the bonding curve, as returned by the quote methods
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct TradeQuote {
+    pub token_amount: Decimal, // the tokens bought or sold
+    pub xrd_amount: Decimal, // for buys the XRD added to the curve, for sells the XRD received by the seller (both excluding fees)
+    pub fee_amount: Decimal, // the tx fee charged on the trade
+    pub fee_perc: Decimal,   // the tx fee % applied to the trade, after any discount
+    pub fee_discount: Decimal, // the discount applied to the tx fee, specified in decimals 50% = 0.5
+    pub end_price: Decimal,    // the price on the curve after the trade
+    pub target_reached: bool, // whether the trade reaches the max market cap, which triggers the listing of the token
+}
+
+// a launch fee schedule where the tx fee starts at start_fee_perc when trading on the token starts and decays to end_fee_perc
+// over the decay period, either linearly (decay_steps = 0) or in the specified number of equal steps
+#[derive(ScryptoSbor, ManifestSbor, Clone, Debug, PartialEq)]
+pub struct FeeSchedule {
+    pub start_fee_perc: Decimal, // the fee % charged when trading starts, specified in decimals 1% = 0.01
+    pub end_fee_perc: Decimal, // the fee % charged once the decay period has passed, specified in decimals 1% = 0.01
+    pub decay_period_mins: u32, // the number of minutes over which the fee decays from the start fee to the end fee
+    pub decay_steps: u32,       // the number of steps in which the fee decays. 0 for a linear decay
+}
+
+impl FeeSchedule {
+    // checks that the fee schedule values are valid
+    pub fn validate(&self) {
+        assert!(
+            self.start_fee_perc >= Decimal::ZERO && self.start_fee_perc < Decimal::ONE,
+            "start_fee_perc must be >= 0 and < 1. start_fee_perc is specified in decimals, e.g. 1% = 0.01."
+        );
+        assert!(
+            self.end_fee_perc >= Decimal::ZERO && self.end_fee_perc < Decimal::ONE,
+            "end_fee_perc must be >= 0 and < 1. end_fee_perc is specified in decimals, e.g. 1% = 0.01."
+        );
+    }
+
+    // calculates the fee % that applies the specified number of seconds after trading on the token started
+    pub fn fee_perc_at(&self, seconds_since_start: i64) -> Decimal {
+        let decay_period_secs = i64::from(self.decay_period_mins) * 60;
+        if decay_period_secs == 0 || seconds_since_start >= decay_period_secs {
+            return self.end_fee_perc;
+        }
+        let elapsed_secs = seconds_since_start.max(0);
+        let progress = if self.decay_steps > 0 {
+            let steps = i64::from(self.decay_steps);
+            Decimal::from(elapsed_secs * steps / decay_period_secs) / Decimal::from(steps)
+        } else {
+            Decimal::from(elapsed_secs) / Decimal::from(decay_period_secs)
+        };
+        self.start_fee_perc - (self.start_fee_perc - self.end_fee_perc) * progress
+    }
+}
+
+// the state of a token curve component that the trade calculations depend on
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct CurveState {
+    pub current_supply: Decimal, // the current supply of tokens on the curve
+    pub xrd_reserve: Decimal,    // the XRD currently held by the curve (excluding fees)
+    pub max_xrd: Decimal,        // the XRD held by the curve when the max market cap is reached
+    pub max_token_supply_to_trade: Decimal, // the max supply of tokens that can be traded on the curve
+    pub tx_fee_perc: Decimal,               // the fee % charged on a trade at this point in time
+    pub fee_discount: Decimal, // the discount on the tx fee for the trader, specified in decimals 50% = 0.5
+}
+
+impl CurveState {
+    // the tx fee % after the fee discount has been applied
+    pub fn fee_perc(&self) -> Decimal {
+        self.tx_fee_perc * (Decimal::ONE - self.fee_discount)
+    }
+}
+
+// returns the highest fee discount from the discount tiers for which the pass amount meets the minimum pass amount
+// each tier is specified as (minimum pass amount, fee discount)
+pub fn pass_fee_discount(discount_tiers: &[(Decimal, Decimal)], pass_amount: Decimal) -> Decimal {
+    let mut fee_discount = Decimal::ZERO;
+    for (min_pass_amount, discount) in discount_tiers {
+        if pass_amount >= *min_pass_amount && *discount > fee_discount {
+            fee_discount = *discount;
+        }
+    }
+    fee_discount
+}
+
+// calculates the result of buying tokens with the specified amount of XRD (including fees)
+pub fn calculate_buy(
+    bonding_curve: &BondingCurveKind,
+    state: &CurveState,
+    xrd_in: Decimal,
+) -> TradeQuote {
+    let (xrd_amount, fee_amount, target_reached) = buy_xrd_amounts(state, xrd_in);
+    let mut token_amount = Decimal::ZERO;
+    if xrd_amount > Decimal::ZERO {
+        token_amount = bonding_curve
+            .calculate_tokens_received(xrd_amount.clone(), state.current_supply.clone());
+        if target_reached {
+            // the last buy receives all remaining tokens, which avoids rounding differences in the curve roots
+            token_amount = state.max_token_supply_to_trade - state.current_supply;
+        }
+        if token_amount + state.current_supply > state.max_token_supply_to_trade {
+            panic!("Unexpected error! Not enough tokens remaining for tx.")
+        }
+    }
+    TradeQuote {
+        token_amount,
+        xrd_amount,
+        fee_amount,
+        fee_perc: state.fee_perc(),
+        fee_discount: state.fee_discount,
+        end_price: bonding_curve.calculate_price(state.current_supply + token_amount),
+        target_reached,
+    }
+}
+
+// calculates the result of a buy during a fair launch with the specified amount of XRD (including fees)
+// no tokens are minted during a fair launch, so the token_amount is zero and the end_price is the price the fair launch
+// would clear at if it ended after this buy. The xrd_reserve of the state is the XRD pooled in the fair launch so far.
+pub fn calculate_fair_launch_buy(
+    bonding_curve: &BondingCurveKind,
+    state: &CurveState,
+    xrd_in: Decimal,
+) -> TradeQuote {
+    let (xrd_amount, fee_amount, target_reached) = buy_xrd_amounts(state, xrd_in);
+    let (clearing_supply, _clearing_price) = fair_launch_clearing(
+        bonding_curve,
+        &CurveState {
+            xrd_reserve: state.xrd_reserve + xrd_amount,
+            ..state.clone()
+        },
+    );
+    TradeQuote {
+        token_amount: Decimal::ZERO,
+        xrd_amount,
+        fee_amount,
+        fee_perc: state.fee_perc(),
+        fee_discount: state.fee_discount,
+        end_price: bonding_curve.calculate_price(clearing_supply),
+        target_reached,
+    }
+}
+
+// calculates the clearing supply and clearing price of a fair launch, where all the XRD pooled during the fair launch
+// (the xrd_reserve of the state) buys tokens from the curve in a single trade from a zero supply.
+// Every fair launch participant receives tokens at the same clearing price, which is the average price of that trade.
+pub fn fair_launch_clearing(
+    bonding_curve: &BondingCurveKind,
+    state: &CurveState,
+) -> (Decimal, Decimal) {
+    let xrd_amount = state.xrd_reserve;
+    if xrd_amount <= Decimal::ZERO {
+        return (Decimal::ZERO, Decimal::ZERO);
+    }
+    let clearing_supply = if xrd_amount >= state.max_xrd {
+        state.max_token_supply_to_trade
+    } else {
+        bonding_curve
+            .calculate_tokens_received(xrd_amount.clone(), Decimal::ZERO)
+            .min(state.max_token_supply_to_trade)
+    };
+    if clearing_supply <= Decimal::ZERO {
+        return (Decimal::ZERO, Decimal::ZERO);
+    }
+    (clearing_supply, xrd_amount / clearing_supply)
+}
+
+// splits the XRD sent for a buy into the XRD added to the curve and the fee, limited to the XRD still available on the curve
+// returns the XRD amount, the fee amount and whether the buy reaches the max market cap
+fn buy_xrd_amounts(state: &CurveState, xrd_in: Decimal) -> (Decimal, Decimal, bool) {
+    let mut xrd_amount = xrd_in;
+    let available_xrd = state.max_xrd - state.xrd_reserve;
+    let mut fee_amount = xrd_amount * state.fee_perc();
+    let mut target_reached = false;
+    if xrd_amount > available_xrd {
+        // calculate fee based on available xrd only
+        fee_amount = available_xrd * state.fee_perc();
+        xrd_amount = xrd_amount - fee_amount;
+        if xrd_amount >= available_xrd {
+            xrd_amount = available_xrd;
+            target_reached = true;
+        }
+    } else {
+        xrd_amount = xrd_amount - fee_amount;
+    };
+    (xrd_amount, fee_amount, target_reached)
+}
+
+// calculates the result of buying the specified amount of tokens
+pub fn calculate_buy_amount(
+    bonding_curve: &BondingCurveKind,
+    state: &CurveState,
+    amount: Decimal,
+) -> TradeQuote {
+    assert!(
+        amount + state.current_supply <= state.max_token_supply_to_trade,
+        "Cannot buy requested amount of tokens. Not enough supply left"
+    );
+    let xrd_required =
+        bonding_curve.calculate_buy_price(amount.clone(), state.current_supply.clone());
+    if xrd_required + state.xrd_reserve > state.max_xrd {
+        panic!("Unexpected error! Max XRD will be exceeded in tx.")
+    }
+    TradeQuote {
+        token_amount: amount,
+        xrd_amount: xrd_required,
+        fee_amount: xrd_required * state.fee_perc(),
+        fee_perc: state.fee_perc(),
+        fee_discount: state.fee_discount,
+        end_price: bonding_curve.calculate_price(state.current_supply + amount),
+        target_reached: amount > Decimal::ZERO && xrd_required + state.xrd_reserve >= state.max_xrd,
+    }
+}
+
+// calculates the result of selling the specified amount of tokens. The xrd_amount is the XRD received after fees.
+pub fn calculate_sell(
+    bonding_curve: &BondingCurveKind,
+    state: &CurveState,
+    token_amount: Decimal,
+) -> TradeQuote {
+    if token_amount > state.current_supply {
+        panic!("Unexpected error! Sending more tokens to sell than current supply.");
+    }
+    let receive_xrd =
+        bonding_curve.calculate_sell_price(token_amount.clone(), state.current_supply.clone());
+    if receive_xrd > state.xrd_reserve {
+        panic!("Unexpected error! Not enough XRD in component for sell tx.")
+    }
+    let fee_amount = receive_xrd * state.fee_perc();
+    TradeQuote {
+        token_amount,
+        xrd_amount: receive_xrd - fee_amount,
+        fee_amount,
+        fee_perc: state.fee_perc(),
+        fee_discount: state.fee_discount,
+        end_price: bonding_curve.calculate_price(state.current_supply - token_amount),
+        target_reached: false,
+    }
+}
+
+// calculates the result of selling tokens to receive the specified amount of XRD after fees
+pub fn calculate_sell_for_xrd_amount(
+    bonding_curve: &BondingCurveKind,
+    state: &CurveState,
+    amount: Decimal,
+) -> TradeQuote {
+    let fee_amount = amount * state.fee_perc();
+    if amount + fee_amount > state.xrd_reserve {
+        panic!("Not enough XRD in component vault for requested amount.");
+    }
+    let tokens_to_sell = bonding_curve.calculate_tokens_to_sell(
+        amount.clone() + fee_amount.clone(),
+        state.current_supply.clone(),
+    );
+    if tokens_to_sell > state.current_supply {
+        panic!("Unexpected error! Not enough token supply in component to sell.");
+    }
+    TradeQuote {
+        token_amount: tokens_to_sell,
+        xrd_amount: amount,
+        fee_amount,
+        fee_perc: state.fee_perc(),
+        fee_discount: state.fee_discount,
+        end_price: bonding_curve.calculate_price(state.current_supply - tokens_to_sell),
+        target_reached: false,
+    }
+}
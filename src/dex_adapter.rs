@@ -0,0 +1,49 @@
+use scrypto::prelude::*;
+
+// the interface between the RadixMemeTokenCurve component and the DEX(s) a token can be listed on when it graduates.
+// A DEX adapter is a component registered by the radix.meme admins on the RadixMemeMain component that exposes a method
+// with the name in DEX_ADAPTER_LIST_METHOD, which takes in (xrd_bucket: Bucket, token_bucket: Bucket)
+// and returns a DexListing: (the pool the liquidity was added to, the LP tokens received, any XRD or tokens that were not used).
+// The token curve does not need to know anything else about the DEX, so new DEXes can be supported by deploying a new adapter.
+
+// the name of the method that token curves call on a DEX adapter at graduation
+pub const DEX_ADAPTER_LIST_METHOD: &str = "list_token";
+
+// the values returned by a DEX adapter when a token is listed
+pub type DexListing = (ComponentAddress, Bucket, Vec<Bucket>);
+
+// lists a token on the DEX of the specified adapter by adding the XRD and tokens as liquidity
+pub fn list_on_dex(
+    adapter_address: ComponentAddress,
+    xrd_bucket: Bucket,
+    token_bucket: Bucket,
+) -> DexListing {
+    let adapter = Global::<AnyComponent>::from(adapter_address);
+    adapter.call_raw::<DexListing>(
+        DEX_ADAPTER_LIST_METHOD,
+        scrypto_args!(xrd_bucket, token_bucket),
+    )
+}
+
+// what happens to the LP tokens a token curve receives when its token is listed at graduation.
+// The LP tokens are always deposited into a RadixMemeLpLock component rather than burned, as burning them would let the next
+// liquidity provider claim the pool. The policy decides whether the lock can ever be opened.
+#[derive(ScryptoSbor, ManifestSbor, Clone, Debug, PartialEq)]
+pub enum LpTokenPolicy {
+    // the LP tokens are locked forever. The withdraw method of the lock is denied to everyone and nobody can change that,
+    // so the liquidity can never be removed
+    LockForever,
+    // the LP tokens are locked for the lock period, after which the radix.meme admins can withdraw them from the lock
+    TimeLock { lock_period_mins: u32 },
+}
+
+// where the LP tokens of a graduated token went, as returned by the token curve
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct LpTokenInfo {
+    pub pool_address: Option<ComponentAddress>, // the pool the token was listed on
+    pub lp_lock_address: Option<ComponentAddress>, // the RadixMemeLpLock component the LP tokens were deposited into
+    pub lp_resource: Option<ResourceAddress>,      // the LP token resource of the pool
+    pub lp_amount: Decimal,                        // the LP tokens held by the lock
+    pub lp_token_policy: LpTokenPolicy, // whether the LP tokens are locked forever or time locked
+    pub unlock_time: Option<i64>, // the time the LP tokens can be withdrawn in seconds since unix epoch, for time locked LP tokens
+}
@@ -1,2 +1,8 @@
+pub mod bonding_curve;
+pub mod curve_math;
+pub mod dex_adapter;
+pub mod mock_dex_adapter;
+pub mod radix_meme_lp_lock;
 pub mod radix_meme_main;
+pub mod radix_meme_pool;
 pub mod radix_meme_token_curve;
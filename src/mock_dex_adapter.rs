@@ -0,0 +1,121 @@
+use scrypto::prelude::*;
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+struct MockDexListEvent {
+    token_address: ResourceAddress,
+    xrd_amount: Decimal,
+    token_amount: Decimal,
+    lp_amount: Decimal,
+}
+
+// a DEX adapter that does not list on a real DEX. It holds the liquidity itself and returns mock LP tokens
+// (one for every XRD added), so the graduation flow through a DEX adapter can be tested without an external DEX.
+// The share of the XRD that the adapter returns unused can be set by the owner of the adapter to test how token curves handle unused liquidity.
+// The adapter implements the interface described in dex_adapter.rs.
+#[blueprint]
+#[events(MockDexListEvent)]
+mod mock_dex_adapter {
+
+    enable_function_auth! {
+        new => AccessRule::AllowAll;
+    }
+
+    enable_method_auth! {
+        methods {
+            list_token => PUBLIC;
+            set_unused_xrd_perc => restrict_to: [OWNER];
+        }
+    }
+
+    struct MockDexAdapter {
+        pub address: ComponentAddress, // the address of this adapter component
+        pub xrd_vaults: KeyValueStore<ResourceAddress, Vault>, // the XRD added as liquidity for each listed token
+        pub token_vaults: KeyValueStore<ResourceAddress, Vault>, // the tokens added as liquidity for each listed token
+        pub lp_manager: ResourceManager, // the resource manager for the mock LP tokens
+        pub tokens_listed: u64,          // the number of tokens listed on the adapter
+        pub unused_xrd_perc: Decimal, // the share of the XRD sent to list_token that is returned unused, specified in decimals 50% = 0.5
+    }
+
+    impl MockDexAdapter {
+        // function to create a new mock DEX adapter, owned by the holders of the owner badge
+        pub fn new(owner_badge_address: ResourceAddress) -> Global<MockDexAdapter> {
+            let (address_reservation, component_address) =
+                Runtime::allocate_component_address(<MockDexAdapter>::blueprint_id());
+            let require_component_rule = rule!(require(global_caller(component_address.clone())));
+            let lp_manager =
+                ResourceBuilder::new_fungible(OwnerRole::Fixed(require_component_rule.clone()))
+                    .divisibility(DIVISIBILITY_MAXIMUM)
+                    .mint_roles(mint_roles! {
+                        minter => require_component_rule.clone();
+                        minter_updater => AccessRule::DenyAll;
+                    })
+                    .metadata(metadata!(
+                        init {
+                            "name" => String::from("Mock DEX LP"), locked;
+                            "symbol" => String::from("MOCK-LP"), locked;
+                        }
+                    ))
+                    .create_with_no_initial_supply();
+
+            MockDexAdapter {
+                address: component_address.clone(),
+                xrd_vaults: KeyValueStore::new(),
+                token_vaults: KeyValueStore::new(),
+                lp_manager,
+                tokens_listed: 0,
+                unused_xrd_perc: Decimal::ZERO,
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Fixed(rule!(require(owner_badge_address))))
+            .with_address(address_reservation)
+            .globalize()
+        }
+
+        // method for the owner to set the share of the XRD sent to list_token that is returned unused
+        pub fn set_unused_xrd_perc(&mut self, unused_xrd_perc: Decimal) {
+            assert!(
+                unused_xrd_perc >= Decimal::ZERO && unused_xrd_perc <= Decimal::ONE,
+                "unused_xrd_perc must be between 0 and 1."
+            );
+            self.unused_xrd_perc = unused_xrd_perc;
+        }
+
+        // method called by token curves at graduation to list their token
+        // the method takes in a bucket of XRD and a bucket of tokens and returns the address of this component as the pool,
+        // the mock LP tokens and a list with the unused XRD (if any) as all tokens are used
+        pub fn list_token(
+            &mut self,
+            mut xrd_bucket: Bucket,
+            token_bucket: Bucket,
+        ) -> (ComponentAddress, Bucket, Vec<Bucket>) {
+            assert!(
+                xrd_bucket.resource_address() == XRD,
+                "First bucket must contain XRD."
+            );
+            let token_address = token_bucket.resource_address();
+            let mut unused_buckets = vec![];
+            if self.unused_xrd_perc > Decimal::ZERO {
+                unused_buckets.push(xrd_bucket.take(xrd_bucket.amount() * self.unused_xrd_perc));
+            }
+            assert!(
+                self.token_vaults.get(&token_address).is_none(),
+                "Token has already been listed."
+            );
+            let xrd_amount = xrd_bucket.amount();
+            let token_amount = token_bucket.amount();
+            let lp_bucket = self.lp_manager.mint(xrd_amount);
+            self.xrd_vaults
+                .insert(token_address.clone(), Vault::with_bucket(xrd_bucket));
+            self.token_vaults
+                .insert(token_address.clone(), Vault::with_bucket(token_bucket));
+            self.tokens_listed += 1;
+            Runtime::emit_event(MockDexListEvent {
+                token_address,
+                xrd_amount,
+                token_amount,
+                lp_amount: xrd_amount,
+            });
+            (self.address.clone(), lp_bucket, unused_buckets)
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use scrypto::prelude::*;
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+struct RadixMemeLpLockWithdrawEvent {
+    lp_resource: ResourceAddress,
+    lp_amount: Decimal,
+}
+
+// a component that holds the LP tokens a token curve receives when its token graduates, so that the token curve does not hold them itself
+// LP tokens without an unlock time are locked forever: the withdraw method is restricted to an admin role set to deny all,
+// and the role has no updaters and the component has no owner, so nobody can ever change it.
+// Time locked LP tokens can be withdrawn by the radix.meme admins once the unlock time has passed.
+#[blueprint]
+#[events(RadixMemeLpLockWithdrawEvent)]
+mod radix_meme_lp_lock {
+
+    enable_function_auth! {
+        new => AccessRule::AllowAll;
+    }
+
+    enable_method_auth! {
+        roles {
+            admin => updatable_by: [];
+        },
+        methods {
+            get_lp_amount => PUBLIC;
+            withdraw_lp_tokens => restrict_to: [admin];
+        }
+    }
+
+    struct RadixMemeLpLock {
+        pub lp_vault: Vault,          // the vault that holds the locked LP tokens
+        pub unlock_time: Option<i64>, // the time the LP tokens can be withdrawn in seconds since unix epoch (None = locked forever)
+    }
+
+    impl RadixMemeLpLock {
+        // function to create a new LP lock holding the LP tokens sent
+        // the function takes in the LP tokens, the unlock time (None to lock them forever) and the access rule of the admins that can withdraw them after the unlock time
+        pub fn new(
+            lp_bucket: Bucket,
+            unlock_time: Option<i64>,
+            admin_rule: AccessRule,
+        ) -> Global<RadixMemeLpLock> {
+            let withdraw_rule = if unlock_time.is_some() {
+                admin_rule
+            } else {
+                AccessRule::DenyAll
+            };
+            RadixMemeLpLock {
+                lp_vault: Vault::with_bucket(lp_bucket),
+                unlock_time,
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .roles(roles! {
+                admin => withdraw_rule;
+            })
+            .globalize()
+        }
+
+        // read only method that returns the amount of LP tokens held by the lock
+        pub fn get_lp_amount(&self) -> Decimal {
+            self.lp_vault.amount()
+        }
+
+        // method for the admins to withdraw time locked LP tokens once the unlock time has passed
+        pub fn withdraw_lp_tokens(&mut self) -> Bucket {
+            let unlock_time = self
+                .unlock_time
+                .expect("LP tokens are locked forever and can never be withdrawn.");
+            let now = Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
+            assert!(
+                now >= unlock_time,
+                "LP tokens are locked until {}. Current time: {}",
+                unlock_time,
+                now
+            );
+            Runtime::emit_event(RadixMemeLpLockWithdrawEvent {
+                lp_resource: self.lp_vault.resource_address(),
+                lp_amount: self.lp_vault.amount(),
+            });
+            self.lp_vault.take_all()
+        }
+    }
+}
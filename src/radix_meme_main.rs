@@ -1,8 +1,17 @@
+use crate::bonding_curve::{BondingCurve, BondingCurveConfig, BondingCurveKind};
+use crate::curve_math::FeeSchedule;
+use crate::dex_adapter::LpTokenPolicy;
 use crate::radix_meme_token_curve::radix_meme_token_curve::{
     RadixMemeTokenCurve, RadixMemeTokenCurveFunctions,
 };
+use crate::radix_meme_token_curve::CurveStatus;
 use scrypto::prelude::*;
 
+#[derive(ScryptoSbor, NonFungibleData)]
+struct ReferrerBadgeData {
+    name: String,
+}
+
 #[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
 struct RadixMemeChangeDefaultEvent {
     field_name: String,
@@ -13,9 +22,52 @@ struct RadixMemeChangeDefaultEvent {
 struct RadixMemeClaimFeeEvent {
     fee_claimed: Decimal,
 }
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+struct RadixMemeReceiveFeeEvent {
+    fee_received: Decimal,
+}
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+struct RadixMemeSweepFeesEvent {
+    fees_swept: Decimal,
+    components_swept: u64,
+}
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+struct RadixMemeRegisterReferrerEvent {
+    referrer: NonFungibleLocalId,
+    name: String,
+}
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+struct RadixMemeReferralFeeEvent {
+    referrer: NonFungibleLocalId,
+    fee_amount: Decimal,
+}
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+struct RadixMemeClaimReferralFeeEvent {
+    referrer: NonFungibleLocalId,
+    fee_claimed: Decimal,
+}
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+struct RadixMemeRegisterDexAdapterEvent {
+    adapter: ComponentAddress,
+    name: String,
+}
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+struct RadixMemeRemoveDexAdapterEvent {
+    adapter: ComponentAddress,
+}
 
 #[blueprint]
-#[events(RadixMemeClaimFeeEvent, RadixMemeChangeDefaultEvent)]
+#[events(
+    RadixMemeClaimFeeEvent,
+    RadixMemeReceiveFeeEvent,
+    RadixMemeSweepFeesEvent,
+    RadixMemeRegisterReferrerEvent,
+    RadixMemeReferralFeeEvent,
+    RadixMemeClaimReferralFeeEvent,
+    RadixMemeRegisterDexAdapterEvent,
+    RadixMemeRemoveDexAdapterEvent,
+    RadixMemeChangeDefaultEvent
+)]
 mod radix_meme_main {
     enable_function_auth! {
         new => AccessRule::AllowAll;
@@ -28,11 +80,27 @@ mod radix_meme_main {
         },
         methods {
             new_token_curve_simple => PUBLIC;
+            new_token_curve_advanced => PUBLIC;
             change_default_parameters => restrict_to: [admin];
             change_default_parameter => restrict_to: [admin];
+            change_default_bonding_curve => restrict_to: [admin];
+            change_default_fee_schedule => restrict_to: [admin];
+            change_pass_fee_discounts => restrict_to: [admin];
+            change_default_fair_launch_cap => restrict_to: [admin];
+            get_pass_fee_discounts => PUBLIC;
             claim_fee_amount => restrict_to: [owner];
             claim_all_fees => restrict_to:[owner];
+            sweep_fees => restrict_to: [owner];
             transfer_fees => PUBLIC;
+            register_referrer => restrict_to: [admin];
+            deposit_referral_fee => PUBLIC;
+            claim_referral_fees => PUBLIC;
+            register_dex_adapter => restrict_to: [admin];
+            remove_dex_adapter => restrict_to: [admin];
+            change_default_dex_adapter => restrict_to: [admin];
+            change_default_lp_token_policy => restrict_to: [admin];
+            update_token_status => PUBLIC;
+            get_token_status => PUBLIC;
         }
     }
 
@@ -42,13 +110,33 @@ mod radix_meme_main {
         pub max_token_supply: Decimal, // the maximum token supply after listing on external dex
         pub max_token_supply_to_trade: Decimal, // the maximum token supply available for trading on the bonding curve
         pub max_xrd_market_cap: Decimal, // the maximum market cap in XRD that will be reached when the max tokens have been traded on the bonding curve
-        pub tokens: KeyValueStore<ComponentAddress, bool>, // a simple list of the tokens launched and whether they are still active
+        pub tokens: KeyValueStore<ComponentAddress, CurveStatus>, // a simple list of the tokens launched and their current lifecycle status
+        pub token_index: KeyValueStore<u64, ComponentAddress>, // the token components in the order they were launched, used to page through all tokens
+        pub token_count: u64,                                  // the number of tokens launched
         pub tx_fee_perc: Decimal, // fee % taken on every tx, specified in decimals 1% = 0.01
+        pub fee_schedule: Option<FeeSchedule>, // optional launch fee schedule used instead of the tx_fee_perc right after a token is created
+        pub pass_resource: Option<ResourceAddress>, // the pass resource that holders can present to receive a discount on tx fees
+        pub pass_fee_discount_tiers: Vec<(Decimal, Decimal)>, // the fee discount tiers for pass holders as (minimum passes held, fee discount), discount specified in decimals 50% = 0.5
+        pub referral_fee_share: Decimal, // share of the protocol part of the tx fee paid to the referrer of a trade, specified in decimals 50% = 0.5
+        pub referrer_badge_manager: ResourceManager, // the resource manager for the referrer badges used to identify referrers and claim referral fees
+        pub referral_vaults: KeyValueStore<NonFungibleLocalId, Vault>, // the referral fees earned by each registered referrer
+        pub referrer_count: u64,        // the number of referrers registered
         pub listing_fee_perc: Decimal, // fee % paid to redix.meme when a token is listed on a dex, specified in decimals 1% = 0.01
         pub creator_fee_perc: Decimal, // fee % paid to the token creator when a token is listed on a dex, specified in decimals 1% = 0.01
+        pub creator_fee_share: Decimal, // share of every tx fee paid to the token creator, specified in decimals 50% = 0.5
+        pub fee_forward_threshold: Option<Decimal>, // token curves forward their fees to the fees_vault once they reach this amount (0 = every trade, None = never)
+        pub pool_fee_perc: Decimal, // swap fee % of the pool a token is listed on when it graduates, specified in decimals 1% = 0.01
+        pub dex_adapters: KeyValueStore<ComponentAddress, String>, // the DEX adapters registered by the admins that tokens can be listed with, with their names
+        pub default_dex_adapter: Option<ComponentAddress>, // the DEX adapter used to list new tokens when they graduate (None = the built-in RadixMemePool)
+        pub lp_token_policy: LpTokenPolicy, // whether new tokens lock forever or time lock the LP tokens received when they graduate
+        pub route_graduated_trades: bool, // whether buys and sells on graduated token curves are routed through the built-in pool instead of being rejected. Routed trades only pay the pool fee and exact amount trades are still rejected
         pub token_creation_fee: Decimal, // XRD fee for creating a token - might be needed for spam protection
         pub fees_vault: Vault,           // vault to hold fees
         pub fair_launch_period_mins: u32, // the number of minutes for a fair launch period
+        pub fair_launch_min_xrd: Decimal, // the min XRD the fair launch of new tokens must raise, below which fair launch receipts are refunded
+        pub fair_launch_max_xrd: Option<Decimal>, // the max XRD a participant can spend during the fair launch period of new tokens (None = no cap)
+        pub fair_launch_badge_resource: Option<ResourceAddress>, // the badge that identifies participants for the fair launch cap (None = the cap applies per buy)
+        pub default_bonding_curve: BondingCurveConfig, // the bonding curve used for tokens created with new_token_curve_simple
     }
 
     impl RadixMemeMain {
@@ -65,9 +153,18 @@ mod radix_meme_main {
             tx_fee_perc: Decimal,
             listing_fee_perc: Decimal,
             creator_fee_perc: Decimal,
+            pool_fee_perc: Decimal,
             token_creation_fee: Decimal,
             owner_badge_address: ResourceAddress,
         ) -> Global<RadixMemeMain> {
+            assert!(
+                max_token_supply > max_token_supply_to_trade,
+                "max_token_supply must be more than max_token_supply_to_trade, so that there are tokens left to list on a pool when a token graduates."
+            );
+            assert!(
+                pool_fee_perc >= Decimal::ZERO && pool_fee_perc < Decimal::ONE,
+                "pool_fee_perc must be >= 0 and < 1."
+            );
             let (address_reservation, component_address) =
                 Runtime::allocate_component_address(<RadixMemeMain>::blueprint_id());
             let dapp_def_account =
@@ -83,6 +180,20 @@ mod radix_meme_main {
             dapp_def_account.set_owner_role(rule!(require(owner_badge_address)));
             let dapp_def_address = GlobalAddress::from(dapp_def_account.address());
 
+            let require_component_rule = rule!(require(global_caller(component_address.clone())));
+            let referrer_badge_manager = ResourceBuilder::new_integer_non_fungible::<ReferrerBadgeData>(OwnerRole::Updatable(rule!(require(owner_badge_address.clone()))))
+            .mint_roles(mint_roles! {
+                minter => require_component_rule.clone();
+                minter_updater => rule!(require(owner_badge_address.clone()));
+            })
+            .metadata(metadata!(
+                init {
+                    "name" => String::from("Radix.meme Referrer Badge"), updatable;
+                    "description" => String::from("Radix.meme referrer badge. Used to identify the referrer of trades and to claim referral fees."), updatable;
+                }
+            ))
+            .create_with_no_initial_supply();
+
             RadixMemeMain {
                 address: component_address,
                 owner_badge_manager: ResourceManager::from_address(owner_badge_address.clone()),
@@ -90,12 +201,35 @@ mod radix_meme_main {
                 max_token_supply_to_trade,
                 max_xrd_market_cap,
                 tx_fee_perc,
+                fee_schedule: None,
+                pass_resource: None,
+                pass_fee_discount_tiers: vec![],
+                referral_fee_share: Decimal::ZERO,
+                referrer_badge_manager,
+                referral_vaults: KeyValueStore::new(),
+                referrer_count: 0,
                 listing_fee_perc,
                 creator_fee_perc,
+                creator_fee_share: Decimal::ZERO,
+                fee_forward_threshold: None,
+                pool_fee_perc,
+                dex_adapters: KeyValueStore::new(),
+                default_dex_adapter: None,
+                lp_token_policy: LpTokenPolicy::LockForever,
+                route_graduated_trades: false,
                 token_creation_fee,
                 tokens: KeyValueStore::new(),
+                token_index: KeyValueStore::new(),
+                token_count: 0,
                 fees_vault: Vault::new(XRD),
                 fair_launch_period_mins,
+                fair_launch_min_xrd: Decimal::ZERO,
+                fair_launch_max_xrd: None,
+                fair_launch_badge_resource: None,
+                default_bonding_curve: BondingCurveConfig::Power {
+                    exponent: dec!(2),
+                    initial_price: Decimal::ZERO,
+                },
             }
             .instantiate()
             .prepare_to_globalize(OwnerRole::Updatable(rule!(require(
@@ -118,7 +252,7 @@ mod radix_meme_main {
             .globalize()
         }
 
-        // function to create an individual token bonding curve component
+        // function to create an individual token bonding curve component using the default bonding curve
         // takes in values used to set up the new token and its bonding curve component
         // returns a global instance of the new component as well as an owner badge for the token.
         pub fn new_token_curve_simple(
@@ -130,6 +264,37 @@ mod radix_meme_main {
             telegram: String,
             x: String,
             website: String,
+            fee_bucket: Bucket,
+        ) -> (Global<RadixMemeTokenCurve>, NonFungibleBucket, Bucket) {
+            self.new_token_curve_advanced(
+                name,
+                symbol,
+                description,
+                icon_url,
+                telegram,
+                x,
+                website,
+                self.default_bonding_curve.clone(),
+                None,
+                fee_bucket,
+            )
+        }
+
+        // function to create an individual token bonding curve component with a bonding curve chosen by the creator
+        // takes in values used to set up the new token as well as the type of bonding curve to use and an optional start time
+        // (seconds since unix epoch) before which all trades are rejected, so that launches can be announced in advance
+        // returns a global instance of the new component as well as an owner badge for the token.
+        pub fn new_token_curve_advanced(
+            &mut self,
+            name: String,
+            symbol: String,
+            description: String,
+            icon_url: String,
+            telegram: String,
+            x: String,
+            website: String,
+            bonding_curve: BondingCurveConfig,
+            start_time: Option<i64>,
             mut fee_bucket: Bucket,
         ) -> (Global<RadixMemeTokenCurve>, NonFungibleBucket, Bucket) {
             if self.token_creation_fee > Decimal::ZERO {
@@ -157,13 +322,29 @@ mod radix_meme_main {
                     self.max_token_supply_to_trade.clone(),
                     self.max_xrd_market_cap.clone(),
                     self.tx_fee_perc.clone(),
+                    self.fee_schedule.clone(),
                     self.listing_fee_perc.clone(),
                     self.creator_fee_perc.clone(),
+                    self.creator_fee_share.clone(),
+                    self.fee_forward_threshold.clone(),
+                    self.pool_fee_perc.clone(),
+                    self.default_dex_adapter.clone(),
+                    self.lp_token_policy.clone(),
+                    self.route_graduated_trades.clone(),
+                    bonding_curve,
+                    start_time,
                     self.fair_launch_period_mins.clone(),
+                    self.fair_launch_min_xrd.clone(),
+                    self.fair_launch_max_xrd.clone(),
+                    self.fair_launch_badge_resource.clone(),
                     self.address.clone(),
                     rule!(require(self.owner_badge_manager.address())),
                 );
-            self.tokens.insert(component_address.clone(), true);
+            self.tokens
+                .insert(component_address.clone(), new_instance.get_status());
+            self.token_index
+                .insert(self.token_count, component_address.clone());
+            self.token_count += 1;
             (new_instance, owner_badge, fee_bucket)
         }
 
@@ -212,20 +393,104 @@ mod radix_meme_main {
                     self.creator_fee_perc = Decimal::try_from(param_value)
                         .expect("Could not convert parameter value for creator_fee_perc to Decimal")
                 }
+                "creator_fee_share" => {
+                    old_value = self.creator_fee_share.to_string();
+                    let creator_fee_share = Decimal::try_from(param_value).expect(
+                        "Could not convert parameter value for creator_fee_share to Decimal",
+                    );
+                    assert!(
+                        creator_fee_share >= Decimal::ZERO && creator_fee_share <= Decimal::ONE,
+                        "creator_fee_share must be between 0 and 1."
+                    );
+                    self.creator_fee_share = creator_fee_share;
+                }
+                "fee_forward_threshold" => {
+                    old_value = format!("{:?}", self.fee_forward_threshold);
+                    // an empty value switches off fee forwarding
+                    self.fee_forward_threshold = if param_value.is_empty() {
+                        None
+                    } else {
+                        let threshold = Decimal::try_from(param_value).expect(
+                            "Could not convert parameter value for fee_forward_threshold to Decimal",
+                        );
+                        assert!(
+                            threshold >= Decimal::ZERO,
+                            "fee_forward_threshold cannot be negative."
+                        );
+                        Some(threshold)
+                    };
+                }
+                "referral_fee_share" => {
+                    old_value = self.referral_fee_share.to_string();
+                    let referral_fee_share = Decimal::try_from(param_value).expect(
+                        "Could not convert parameter value for referral_fee_share to Decimal",
+                    );
+                    assert!(
+                        referral_fee_share >= Decimal::ZERO && referral_fee_share <= Decimal::ONE,
+                        "referral_fee_share must be between 0 and 1."
+                    );
+                    self.referral_fee_share = referral_fee_share;
+                }
+                "pool_fee_perc" => {
+                    old_value = self.pool_fee_perc.to_string();
+                    let pool_fee_perc = Decimal::try_from(param_value)
+                        .expect("Could not convert parameter value for pool_fee_perc to Decimal");
+                    assert!(
+                        pool_fee_perc >= Decimal::ZERO && pool_fee_perc < Decimal::ONE,
+                        "pool_fee_perc must be >= 0 and < 1."
+                    );
+                    self.pool_fee_perc = pool_fee_perc;
+                }
                 "token_creation_fee" => {
                     old_value = self.token_creation_fee.to_string();
                     self.token_creation_fee = Decimal::try_from(param_value).expect(
                         "Could not convert parameter value for token_creation_fee to Decimal",
                     )
                 }
+                "initial_price" => {
+                    let initial_price = Decimal::try_from(param_value)
+                        .expect("Could not convert parameter value for initial_price to Decimal");
+                    let new_bonding_curve = match self.default_bonding_curve.clone() {
+                        BondingCurveConfig::Power { exponent, .. } => BondingCurveConfig::Power {
+                            exponent,
+                            initial_price,
+                        },
+                        BondingCurveConfig::Linear { .. } => {
+                            BondingCurveConfig::Linear { initial_price }
+                        }
+                        BondingCurveConfig::VirtualReserve { .. } => panic!(
+                            "initial_price cannot be set for a virtual reserve bonding curve."
+                        ),
+                    };
+                    old_value = format!("{:?}", self.default_bonding_curve);
+                    self.default_bonding_curve = new_bonding_curve;
+                }
+                "route_graduated_trades" => {
+                    old_value = self.route_graduated_trades.to_string();
+                    self.route_graduated_trades = param_value.parse().expect(
+                        "Could not convert parameter value for route_graduated_trades to bool",
+                    );
+                }
                 "fair_launch_period_mins" => {
                     old_value = self.fair_launch_period_mins.to_string();
                     self.fair_launch_period_mins = param_value.parse().expect(
                         "Could not convert parameter value for fair_launch_period_mins to u32",
                     );
                 }
+                "fair_launch_min_xrd" => {
+                    old_value = self.fair_launch_min_xrd.to_string();
+                    let fair_launch_min_xrd = Decimal::try_from(param_value).expect(
+                        "Could not convert parameter value for fair_launch_min_xrd to Decimal",
+                    );
+                    assert!(
+                        fair_launch_min_xrd >= Decimal::ZERO,
+                        "fair_launch_min_xrd cannot be negative."
+                    );
+                    self.fair_launch_min_xrd = fair_launch_min_xrd;
+                }
                 _ => panic!("Could not match parameter name"),
             };
+            self.check_default_parameters();
             Runtime::emit_event(RadixMemeChangeDefaultEvent {
                 field_name: param_name.clone(),
                 old_value,
@@ -233,6 +498,171 @@ mod radix_meme_main {
             });
         }
 
+        // method to change the bonding curve used for tokens created with new_token_curve_simple
+        // the curve is checked against the current default max supply and market cap values before it is accepted
+        pub fn change_default_bonding_curve(&mut self, bonding_curve: BondingCurveConfig) {
+            Runtime::emit_event(RadixMemeChangeDefaultEvent {
+                field_name: String::from("default_bonding_curve"),
+                old_value: format!("{:?}", self.default_bonding_curve),
+                new_value: format!("{:?}", bonding_curve),
+            });
+            self.default_bonding_curve = bonding_curve;
+            self.check_default_parameters();
+        }
+
+        // method to change the launch fee schedule used for new tokens. None switches the fee schedule off.
+        pub fn change_default_fee_schedule(&mut self, fee_schedule: Option<FeeSchedule>) {
+            if let Some(schedule) = fee_schedule.as_ref() {
+                schedule.validate();
+            }
+            Runtime::emit_event(RadixMemeChangeDefaultEvent {
+                field_name: String::from("fee_schedule"),
+                old_value: format!("{:?}", self.fee_schedule),
+                new_value: format!("{:?}", fee_schedule),
+            });
+            self.fee_schedule = fee_schedule;
+        }
+
+        // method to set the pass resource and fee discount tiers used by all token curves
+        // each tier is specified as (minimum passes held, fee discount). The highest discount for which the pass holder qualifies is applied.
+        pub fn change_pass_fee_discounts(
+            &mut self,
+            pass_resource: Option<ResourceAddress>,
+            discount_tiers: Vec<(Decimal, Decimal)>,
+        ) {
+            for (min_pass_amount, discount) in discount_tiers.iter() {
+                assert!(
+                    *min_pass_amount >= Decimal::ZERO,
+                    "Minimum pass amount for a fee discount tier cannot be negative."
+                );
+                assert!(
+                    *discount >= Decimal::ZERO && *discount <= Decimal::ONE,
+                    "Fee discount must be between 0 and 1. Fee discount is specified in decimals, e.g. 50% = 0.5."
+                );
+            }
+            Runtime::emit_event(RadixMemeChangeDefaultEvent {
+                field_name: String::from("pass_fee_discounts"),
+                old_value: format!(
+                    "{:?} {:?}",
+                    self.pass_resource, self.pass_fee_discount_tiers
+                ),
+                new_value: format!("{:?} {:?}", pass_resource, discount_tiers),
+            });
+            self.pass_resource = pass_resource;
+            self.pass_fee_discount_tiers = discount_tiers;
+        }
+
+        // method to set the max XRD a participant can spend during the fair launch period of new tokens. None switches the cap off.
+        // if a badge resource is set, participants must present a proof of the badge to buy during the fair launch, and the cap applies
+        // to all buys made with the same badge. Without a badge resource, the cap applies to every buy separately.
+        pub fn change_default_fair_launch_cap(
+            &mut self,
+            max_xrd: Option<Decimal>,
+            badge_resource: Option<ResourceAddress>,
+        ) {
+            if let Some(max_xrd_amount) = max_xrd.as_ref() {
+                assert!(
+                    *max_xrd_amount > Decimal::ZERO,
+                    "Fair launch max XRD must be more than 0."
+                );
+            }
+            if let Some(badge_address) = badge_resource.as_ref() {
+                assert!(
+                    !badge_address.is_fungible(),
+                    "The fair launch badge resource must be non-fungible, so that each badge identifies a single participant."
+                );
+            }
+            Runtime::emit_event(RadixMemeChangeDefaultEvent {
+                field_name: String::from("fair_launch_cap"),
+                old_value: format!(
+                    "{:?} {:?}",
+                    self.fair_launch_max_xrd, self.fair_launch_badge_resource
+                ),
+                new_value: format!("{:?} {:?}", max_xrd, badge_resource),
+            });
+            self.fair_launch_max_xrd = max_xrd;
+            self.fair_launch_badge_resource = badge_resource;
+        }
+
+        // read only method that returns the pass resource and fee discount tiers, used by the token curves
+        pub fn get_pass_fee_discounts(&self) -> (Option<ResourceAddress>, Vec<(Decimal, Decimal)>) {
+            (
+                self.pass_resource.clone(),
+                self.pass_fee_discount_tiers.clone(),
+            )
+        }
+
+        // method to register a DEX adapter that tokens can be listed with when they graduate
+        // the adapter must implement the interface described in dex_adapter.rs
+        pub fn register_dex_adapter(&mut self, adapter: ComponentAddress, name: String) {
+            assert!(
+                self.dex_adapters.get(&adapter).is_none(),
+                "DEX adapter is already registered."
+            );
+            self.dex_adapters.insert(adapter.clone(), name.clone());
+            Runtime::emit_event(RadixMemeRegisterDexAdapterEvent { adapter, name });
+        }
+
+        // method to remove a registered DEX adapter. If it is the default adapter, new tokens will use the built-in pool.
+        // tokens that were created with the adapter will still use it when they graduate
+        pub fn remove_dex_adapter(&mut self, adapter: ComponentAddress) {
+            assert!(
+                self.dex_adapters.get(&adapter).is_some(),
+                "DEX adapter is not registered."
+            );
+            self.dex_adapters.remove(&adapter);
+            if self.default_dex_adapter == Some(adapter.clone()) {
+                self.change_default_dex_adapter(None);
+            }
+            Runtime::emit_event(RadixMemeRemoveDexAdapterEvent { adapter });
+        }
+
+        // method to set the DEX adapter that new tokens will be listed with when they graduate. None = the built-in RadixMemePool
+        pub fn change_default_dex_adapter(&mut self, adapter: Option<ComponentAddress>) {
+            if let Some(adapter_address) = adapter.as_ref() {
+                assert!(
+                    self.dex_adapters.get(adapter_address).is_some(),
+                    "DEX adapter is not registered."
+                );
+            }
+            Runtime::emit_event(RadixMemeChangeDefaultEvent {
+                field_name: String::from("default_dex_adapter"),
+                old_value: format!("{:?}", self.default_dex_adapter),
+                new_value: format!("{:?}", adapter),
+            });
+            self.default_dex_adapter = adapter;
+        }
+
+        // method to change whether new tokens lock forever or time lock the LP tokens received when they graduate
+        pub fn change_default_lp_token_policy(&mut self, lp_token_policy: LpTokenPolicy) {
+            Runtime::emit_event(RadixMemeChangeDefaultEvent {
+                field_name: String::from("lp_token_policy"),
+                old_value: format!("{:?}", self.lp_token_policy),
+                new_value: format!("{:?}", lp_token_policy),
+            });
+            self.lp_token_policy = lp_token_policy;
+        }
+
+        // method used by the token curves to keep the status of each token curve up to date in the tokens list
+        // only the token curve component itself can update its status. Time driven status changes (such as the start time or
+        // the end of the fair launch period) are stored with the next tx on the token curve, or by calling its refresh_status method
+        pub fn update_token_status(&mut self, component: ComponentAddress, status: CurveStatus) {
+            Runtime::assert_access_rule(rule!(require(global_caller(component.clone()))));
+            let mut token_status = self
+                .tokens
+                .get_mut(&component)
+                .expect("Token curve is not registered on this component.");
+            *token_status = status;
+        }
+
+        // read only method that returns the status of a token curve as kept in the tokens list
+        pub fn get_token_status(&self, component: ComponentAddress) -> CurveStatus {
+            *self
+                .tokens
+                .get(&component)
+                .expect("Token curve is not registered on this component.")
+        }
+
         pub fn claim_fee_amount(&mut self, amount: Decimal) -> Bucket {
             assert!(
                 amount <= self.fees_vault.amount(),
@@ -252,12 +682,134 @@ mod radix_meme_main {
             self.fees_vault.take_all()
         }
 
+        // method to collect the fees from a range of token components into the fees_vault
+        // takes in the index of the first token component (in launch order) and the max number of components to sweep
+        pub fn sweep_fees(&mut self, start_index: u64, max_components: u64) {
+            let end_index = start_index
+                .saturating_add(max_components)
+                .min(self.token_count);
+            let mut fees_swept = Decimal::ZERO;
+            let mut components_swept: u64 = 0;
+            for index in start_index..end_index {
+                let component_address = self
+                    .token_index
+                    .get(&index)
+                    .expect("Could not find token component for index.")
+                    .clone();
+                let token_curve = Global::<RadixMemeTokenCurve>::from(component_address);
+                let fee_bucket = token_curve.claim_all_fees();
+                fees_swept = fees_swept + fee_bucket.amount();
+                self.fees_vault.put(fee_bucket);
+                components_swept += 1;
+            }
+            Runtime::emit_event(RadixMemeSweepFeesEvent {
+                fees_swept,
+                components_swept,
+            });
+        }
+
+        // method to register a new referrer. Returns the referrer badge, whose id is used as the referrer on trades and which is needed to claim referral fees.
+        pub fn register_referrer(&mut self, name: String) -> Bucket {
+            self.referrer_count += 1;
+            let referrer = NonFungibleLocalId::integer(self.referrer_count);
+            let referrer_badge = self
+                .referrer_badge_manager
+                .mint_non_fungible(&referrer, ReferrerBadgeData { name: name.clone() });
+            self.referral_vaults
+                .insert(referrer.clone(), Vault::new(XRD));
+            Runtime::emit_event(RadixMemeRegisterReferrerEvent { referrer, name });
+            referrer_badge
+        }
+
+        // method used by the token curves to pay the referral share of a tx fee to a referrer
+        // the referral share is added to the referrer's balance and the rest of the fee is returned
+        // only a token curve registered on this component can call the method, for its own component address
+        pub fn deposit_referral_fee(
+            &mut self,
+            component: ComponentAddress,
+            referrer: NonFungibleLocalId,
+            mut fee_bucket: Bucket,
+        ) -> Bucket {
+            Runtime::assert_access_rule(rule!(require(global_caller(component.clone()))));
+            assert!(
+                self.tokens.get(&component).is_some(),
+                "Token curve is not registered on this component."
+            );
+            assert!(
+                fee_bucket.resource_address() == XRD,
+                "Can only deposit XRD referral fees."
+            );
+            let referral_fee = fee_bucket.amount() * self.referral_fee_share;
+            let mut referral_vault = self
+                .referral_vaults
+                .get_mut(&referrer)
+                .expect("Referrer is not registered.");
+            if referral_fee > Decimal::ZERO {
+                referral_vault.put(fee_bucket.take(referral_fee));
+                Runtime::emit_event(RadixMemeReferralFeeEvent {
+                    referrer,
+                    fee_amount: referral_fee,
+                });
+            }
+            fee_bucket
+        }
+
+        // method for a referrer to withdraw their referral fees. Takes in a proof of the referrer badge.
+        pub fn claim_referral_fees(&mut self, referrer_proof: Proof) -> Bucket {
+            let checked_proof = referrer_proof.check_with_message(
+                self.referrer_badge_manager.address(),
+                "Incorrect proof submitted. Proof of a referrer badge is required.",
+            );
+            let referrer = checked_proof.as_non_fungible().non_fungible_local_id();
+            let mut referral_vault = self
+                .referral_vaults
+                .get_mut(&referrer)
+                .expect("Referrer is not registered.");
+            let out_bucket = referral_vault.take_all();
+            Runtime::emit_event(RadixMemeClaimReferralFeeEvent {
+                referrer: referrer.clone(),
+                fee_claimed: out_bucket.amount(),
+            });
+            out_bucket
+        }
+
         pub fn transfer_fees(&mut self, in_bucket: Bucket) {
             assert!(
                 in_bucket.resource_address() == XRD,
                 "Can only transfer XRD fees to the RadixMemeMain component."
             );
+            Runtime::emit_event(RadixMemeReceiveFeeEvent {
+                fee_received: in_bucket.amount(),
+            });
             self.fees_vault.put(in_bucket);
         }
+
+        // checks that the default parameters are consistent with each other, so that tokens can be created with new_token_curve_simple.
+        // Called after every change to the defaults, as a change to one parameter can invalidate another, e.g. a lower
+        // max_xrd_market_cap can put the max XRD below the fair_launch_min_xrd
+        fn check_default_parameters(&self) {
+            assert!(
+                self.max_token_supply > self.max_token_supply_to_trade,
+                "max_token_supply must be more than max_token_supply_to_trade."
+            );
+            let bonding_curve = BondingCurveKind::new(
+                self.default_bonding_curve.clone(),
+                self.max_xrd_market_cap.clone(),
+                self.max_token_supply_to_trade.clone(),
+            );
+            let max_xrd = bonding_curve.calculate_max_xrd(self.max_token_supply_to_trade.clone());
+            assert!(
+                self.fair_launch_min_xrd <= max_xrd,
+                "fair_launch_min_xrd cannot be more than the max XRD a token curve with the default parameters can raise. Max XRD: {}",
+                max_xrd
+            );
+            let pool_xrd = max_xrd * (Decimal::ONE - self.listing_fee_perc - self.creator_fee_perc);
+            assert!(
+                pool_xrd
+                    <= (self.max_token_supply - self.max_token_supply_to_trade)
+                        * bonding_curve.calculate_price(self.max_token_supply_to_trade),
+                "max_token_supply is too low to list the token on a pool at the final price of the default bonding curve."
+            );
+        }
     }
 }
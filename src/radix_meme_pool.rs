@@ -0,0 +1,339 @@
+use scrypto::prelude::*;
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+struct RadixMemePoolSwapEvent {
+    input_address: ResourceAddress,
+    input_amount: Decimal,
+    output_address: ResourceAddress,
+    output_amount: Decimal,
+    fee_amount: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+struct RadixMemePoolAddLiquidityEvent {
+    xrd_amount: Decimal,
+    token_amount: Decimal,
+    lp_amount: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+struct RadixMemePoolRemoveLiquidityEvent {
+    xrd_amount: Decimal,
+    token_amount: Decimal,
+    lp_amount: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+struct RadixMemePoolChangeFeeEvent {
+    old_fee_perc: Decimal,
+    new_fee_perc: Decimal,
+}
+
+// a constant product (x * y = k) pool between XRD and a graduated radix.meme token
+// the pool is created by a token curve component when its token graduates and seeded with the XRD raised on the curve
+// LP tokens represent a share of the pool reserves and the swap fee stays in the pool, so it accrues to the LP token holders
+#[blueprint]
+#[events(
+    RadixMemePoolSwapEvent,
+    RadixMemePoolAddLiquidityEvent,
+    RadixMemePoolRemoveLiquidityEvent,
+    RadixMemePoolChangeFeeEvent
+)]
+mod radix_meme_pool {
+
+    enable_function_auth! {
+        new => AccessRule::AllowAll;
+    }
+
+    enable_method_auth! {
+        roles {
+            admin => updatable_by: [admin];
+        },
+        methods {
+            swap => PUBLIC;
+            quote_swap => PUBLIC;
+            get_reserves => PUBLIC;
+            add_liquidity => PUBLIC;
+            remove_liquidity => PUBLIC;
+            set_fee_perc => restrict_to: [admin];
+        }
+    }
+
+    struct RadixMemePool {
+        pub address: ComponentAddress,   // the address of this pool component
+        pub xrd_vault: Vault,            // the XRD reserve of the pool
+        pub token_vault: Vault,          // the token reserve of the pool
+        pub lp_manager: ResourceManager, // the resource manager for the LP tokens of the pool
+        pub fee_perc: Decimal, // fee % taken from the input of every swap, specified in decimals 1% = 0.01
+    }
+
+    impl RadixMemePool {
+        // function to create a new pool, seeded with the XRD and tokens sent
+        // the function takes in a bucket of XRD, a bucket of the token to pair with XRD, the swap fee % and the access rule for the pool admin
+        // the function returns a global instance of the pool and a bucket with the LP tokens for the initial liquidity
+        pub fn new(
+            xrd_bucket: Bucket,
+            token_bucket: Bucket,
+            fee_perc: Decimal,
+            admin_rule: AccessRule,
+        ) -> (Global<RadixMemePool>, Bucket) {
+            assert!(
+                xrd_bucket.resource_address() == XRD,
+                "Pool must be seeded with XRD."
+            );
+            assert!(
+                token_bucket.resource_address() != XRD,
+                "Pool token cannot be XRD."
+            );
+            assert!(
+                xrd_bucket.amount() > Decimal::ZERO && token_bucket.amount() > Decimal::ZERO,
+                "Pool must be seeded with both XRD and tokens."
+            );
+            assert!(fee_perc >= Decimal::ZERO && fee_perc < Decimal::ONE, "fee_perc must be >= 0 and < 1. fee_perc is specified in decimals, e.g. 1% = 0.01. ");
+            let (address_reservation, component_address) =
+                Runtime::allocate_component_address(<RadixMemePool>::blueprint_id());
+            let require_component_rule = rule!(require(global_caller(component_address.clone())));
+            let token_manager = ResourceManager::from_address(token_bucket.resource_address());
+            let token_symbol: String = token_manager
+                .get_metadata("symbol")
+                .unwrap_or(None)
+                .unwrap_or(String::from("TOKEN"));
+
+            let lp_manager = ResourceBuilder::new_fungible(OwnerRole::Fixed(require_component_rule.clone()))
+            .divisibility(DIVISIBILITY_MAXIMUM)
+            .mint_roles(mint_roles! {
+                minter => require_component_rule.clone();
+                minter_updater => AccessRule::DenyAll;
+            })
+            .burn_roles(burn_roles! {
+                burner => require_component_rule.clone();
+                burner_updater => AccessRule::DenyAll;
+            })
+            .metadata(metadata!(
+                init {
+                    "name" => format!("Radix.meme {}/XRD LP", token_symbol.clone()), updatable;
+                    "symbol" => format!("{}-XRD-LP", token_symbol.clone()), locked;
+                    "description" => format!("Radix.meme LP token for the {}/XRD pool. This token represents a share of the pool reserves.", token_symbol.clone()), locked;
+                    "radix_meme_pool" => format!("{:?}", component_address.clone()), locked;
+                }
+            ))
+            .create_with_no_initial_supply();
+
+            let xrd_amount = xrd_bucket.amount();
+            let token_amount = token_bucket.amount();
+            let lp_amount = Self::initial_lp_amount(xrd_amount, token_amount);
+            let lp_bucket = lp_manager.mint(lp_amount);
+
+            let new_pool = RadixMemePool {
+                address: component_address.clone(),
+                xrd_vault: Vault::with_bucket(xrd_bucket),
+                token_vault: Vault::with_bucket(token_bucket),
+                lp_manager,
+                fee_perc,
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Updatable(admin_rule.clone()))
+            .with_address(address_reservation)
+            .roles(roles! {
+                admin => admin_rule.clone();
+            })
+            .metadata(metadata! {
+                init {
+                    "name" => format!("Radix.meme: {}/XRD Pool", token_symbol.clone()), updatable;
+                    "description" => format!("Radix Meme constant product pool for token {}", token_symbol.clone()), updatable;
+                    "info_url" => Url::of(String::from("https://radix.meme")), updatable;
+                    "tags" => vec!["RadixMeme", "Pool"], updatable;
+                }
+            })
+            .globalize();
+            Runtime::emit_event(RadixMemePoolAddLiquidityEvent {
+                xrd_amount,
+                token_amount,
+                lp_amount,
+            });
+            (new_pool, lp_bucket)
+        }
+
+        // method to swap XRD for tokens or tokens for XRD
+        // the method takes in a bucket of either XRD or the pool token and returns a bucket with the other resource
+        pub fn swap(&mut self, in_bucket: Bucket) -> Bucket {
+            let input_address = in_bucket.resource_address();
+            let input_amount = in_bucket.amount();
+            let (output_amount, fee_amount) = self.quote_swap(input_address, input_amount);
+            assert!(
+                output_amount > Decimal::ZERO,
+                "Swap amount too small. No tokens would be received."
+            );
+            let out_bucket = if input_address == XRD {
+                self.xrd_vault.put(in_bucket);
+                self.token_vault.take(output_amount)
+            } else {
+                self.token_vault.put(in_bucket);
+                self.xrd_vault.take(output_amount)
+            };
+            Runtime::emit_event(RadixMemePoolSwapEvent {
+                input_address,
+                input_amount,
+                output_address: out_bucket.resource_address(),
+                output_amount,
+                fee_amount,
+            });
+            out_bucket
+        }
+
+        // method that returns the amount that will be received for swapping the specified amount of XRD or tokens
+        // the method returns the output amount as well as the fee that will be charged on the input amount
+        pub fn quote_swap(
+            &self,
+            input_address: ResourceAddress,
+            input_amount: Decimal,
+        ) -> (Decimal, Decimal) {
+            let (input_reserve, output_reserve) = if input_address == XRD {
+                (self.xrd_vault.amount(), self.token_vault.amount())
+            } else if input_address == self.token_vault.resource_address() {
+                (self.token_vault.amount(), self.xrd_vault.amount())
+            } else {
+                panic!("Wrong resource for swap. Only XRD or the pool token can be swapped.");
+            };
+            assert!(
+                input_amount > Decimal::ZERO,
+                "Swap amount must be positive."
+            );
+            assert!(
+                input_reserve > Decimal::ZERO && output_reserve > Decimal::ZERO,
+                "Pool has no liquidity."
+            );
+            let fee_amount = input_amount * self.fee_perc;
+            let precise_input = PreciseDecimal::from(input_amount - fee_amount);
+            let precise_output = PreciseDecimal::from(output_reserve)
+                .checked_mul(precise_input)
+                .expect("quote_swap problem. First mul")
+                .checked_div(PreciseDecimal::from(input_reserve) + precise_input)
+                .expect("quote_swap problem. First div");
+            // the output is rounded down so that rounding never reduces the pool reserves
+            let output_amount = Decimal::try_from(
+                precise_output
+                    .checked_round(18, RoundingMode::ToZero)
+                    .expect("quote_swap problem. Cant round precise decimal."),
+            )
+            .expect("quote_swap problem. Cant convert precise decimal.");
+            (output_amount, fee_amount)
+        }
+
+        // read only method that returns the XRD and token reserves of the pool
+        pub fn get_reserves(&self) -> (Decimal, Decimal) {
+            (self.xrd_vault.amount(), self.token_vault.amount())
+        }
+
+        // method to add liquidity to the pool
+        // the method takes in a bucket of XRD and a bucket of tokens. Liquidity is added in the ratio of the pool reserves
+        // the method returns a bucket of LP tokens as well as the XRD and tokens that were not needed to add the liquidity
+        pub fn add_liquidity(
+            &mut self,
+            mut xrd_bucket: Bucket,
+            mut token_bucket: Bucket,
+        ) -> (Bucket, Bucket, Bucket) {
+            assert!(
+                xrd_bucket.resource_address() == XRD,
+                "Wrong resource sent. First bucket must contain XRD."
+            );
+            assert!(
+                token_bucket.resource_address() == self.token_vault.resource_address(),
+                "Wrong resource sent. Second bucket must contain the pool token."
+            );
+            let xrd_reserve = self.xrd_vault.amount();
+            let token_reserve = self.token_vault.amount();
+            let lp_supply = self
+                .lp_manager
+                .total_supply()
+                .expect("Could not get LP token supply.");
+            let (xrd_amount, token_amount, lp_amount) = if lp_supply == Decimal::ZERO
+                || xrd_reserve == Decimal::ZERO
+                || token_reserve == Decimal::ZERO
+            {
+                // all liquidity has been removed, so the new liquidity sets the pool price
+                let xrd_amount = xrd_bucket.amount();
+                let token_amount = token_bucket.amount();
+                (
+                    xrd_amount,
+                    token_amount,
+                    Self::initial_lp_amount(xrd_amount, token_amount),
+                )
+            } else {
+                let ratio = std::cmp::min(
+                    xrd_bucket.amount() / xrd_reserve,
+                    token_bucket.amount() / token_reserve,
+                );
+                (
+                    std::cmp::min(xrd_reserve * ratio, xrd_bucket.amount()),
+                    std::cmp::min(token_reserve * ratio, token_bucket.amount()),
+                    lp_supply * ratio,
+                )
+            };
+            assert!(
+                lp_amount > Decimal::ZERO,
+                "Liquidity amount too small. No LP tokens would be received."
+            );
+            self.xrd_vault.put(xrd_bucket.take(xrd_amount));
+            self.token_vault.put(token_bucket.take(token_amount));
+            Runtime::emit_event(RadixMemePoolAddLiquidityEvent {
+                xrd_amount,
+                token_amount,
+                lp_amount,
+            });
+            (self.lp_manager.mint(lp_amount), xrd_bucket, token_bucket)
+        }
+
+        // method to remove liquidity from the pool
+        // the method takes in a bucket of LP tokens and returns the share of the XRD and token reserves that they represent
+        pub fn remove_liquidity(&mut self, lp_bucket: Bucket) -> (Bucket, Bucket) {
+            assert!(
+                lp_bucket.resource_address() == self.lp_manager.address(),
+                "Wrong resource sent. Only LP tokens for this pool can be sent."
+            );
+            let lp_amount = lp_bucket.amount();
+            let lp_supply = self
+                .lp_manager
+                .total_supply()
+                .expect("Could not get LP token supply.");
+            let share = lp_amount / lp_supply;
+            let xrd_amount = self.xrd_vault.amount() * share;
+            let token_amount = self.token_vault.amount() * share;
+            lp_bucket.burn();
+            Runtime::emit_event(RadixMemePoolRemoveLiquidityEvent {
+                xrd_amount,
+                token_amount,
+                lp_amount,
+            });
+            (
+                self.xrd_vault.take(xrd_amount),
+                self.token_vault.take(token_amount),
+            )
+        }
+
+        // method to change the swap fee of the pool
+        pub fn set_fee_perc(&mut self, fee_perc: Decimal) {
+            assert!(fee_perc >= Decimal::ZERO && fee_perc < Decimal::ONE, "fee_perc must be >= 0 and < 1. fee_perc is specified in decimals, e.g. 1% = 0.01. ");
+            Runtime::emit_event(RadixMemePoolChangeFeeEvent {
+                old_fee_perc: self.fee_perc,
+                new_fee_perc: fee_perc,
+            });
+            self.fee_perc = fee_perc;
+        }
+
+        // the LP tokens minted for the first liquidity are the geometric mean of the XRD and token amounts
+        fn initial_lp_amount(xrd_amount: Decimal, token_amount: Decimal) -> Decimal {
+            let precise_lp_amount = PreciseDecimal::from(xrd_amount)
+                .checked_mul(PreciseDecimal::from(token_amount))
+                .expect("initial_lp_amount problem. First mul")
+                .checked_nth_root(2)
+                .expect("initial_lp_amount problem. First root");
+            Decimal::try_from(
+                precise_lp_amount
+                    .checked_round(18, RoundingMode::ToZero)
+                    .expect("initial_lp_amount problem. Cant round precise decimal."),
+            )
+            .expect("initial_lp_amount problem. Cant convert precise decimal.")
+        }
+    }
+}
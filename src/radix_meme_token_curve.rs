@@ -1,4 +1,10 @@
+use crate::bonding_curve::{BondingCurve, BondingCurveConfig, BondingCurveKind};
+pub use crate::curve_math::TradeQuote;
+use crate::curve_math::{self, CurveState, FeeSchedule};
+use crate::dex_adapter::{self, LpTokenInfo, LpTokenPolicy};
+use crate::radix_meme_lp_lock::radix_meme_lp_lock::{RadixMemeLpLock, RadixMemeLpLockFunctions};
 use crate::radix_meme_main::radix_meme_main::RadixMemeMain;
+use crate::radix_meme_pool::radix_meme_pool::{RadixMemePool, RadixMemePoolFunctions};
 use scrypto::prelude::*;
 
 #[derive(ScryptoSbor, NonFungibleData)]
@@ -12,10 +18,51 @@ struct FairLaunchReceiptData {
     xrd_amount: Decimal,
 }
 
+// the lifecycle status of a token curve. The parent component keeps a copy of the status of every token curve.
+#[derive(ScryptoSbor, ManifestSbor, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveStatus {
+    Scheduled,  // the token has been created, but trading only starts at its start time
+    FairLaunch, // the token is in its fair launch period. Buys pool XRD for fair launch receipts and sells are not allowed
+    Trading,    // the token is traded on the bonding curve
+    Graduating, // the target market cap has been reached and the token is being listed
+    Graduated,  // the token has been listed and is no longer traded on the bonding curve
+    Paused,     // all trading has been paused by the radix.meme admins
+    WoundDown, // the token has been wound down by the radix.meme admins. Only sells and fair launch claims are allowed
+    Refunding, // the fair launch raised less than the fair launch min XRD. Fair launch receipts can only be refunded
+}
+
+// the statuses in which trades and fair launch claims are allowed
+const BUY_STATUSES: [CurveStatus; 2] = [CurveStatus::FairLaunch, CurveStatus::Trading];
+const SELL_STATUSES: [CurveStatus; 2] = [CurveStatus::Trading, CurveStatus::WoundDown];
+const CLAIM_STATUSES: [CurveStatus; 3] = [
+    CurveStatus::Trading,
+    CurveStatus::Graduated,
+    CurveStatus::WoundDown,
+];
+// the statuses in which fees can be claimed and the fee forward threshold changed. Fees already earned stay claimable when a token
+// is paused, wound down or refunding, so every status is allowed except Graduating, which only lasts while the token is being listed
+const FEE_STATUSES: [CurveStatus; 7] = [
+    CurveStatus::Scheduled,
+    CurveStatus::FairLaunch,
+    CurveStatus::Trading,
+    CurveStatus::Graduated,
+    CurveStatus::Paused,
+    CurveStatus::WoundDown,
+    CurveStatus::Refunding,
+];
+
 #[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
 struct RadixMemeTokenCreateEvent {
     token_address: ResourceAddress,
     component_address: ComponentAddress,
+    start_time: i64,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+struct RadixMemeStatusChangeEvent {
+    token_address: ResourceAddress,
+    old_status: CurveStatus,
+    new_status: CurveStatus,
 }
 
 #[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
@@ -25,9 +72,61 @@ struct RadixMemeTokenTradeEvent {
     fair_launch_period: bool,
     token_amount: Decimal,
     xrd_amount: Decimal,
+    fee_perc: Decimal,
+    fee_discount: Decimal,
+    fee_amount: Decimal,
     end_price: Decimal,
 }
 
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+struct RadixMemeFairLaunchClearedEvent {
+    token_address: ResourceAddress,
+    xrd_amount: Decimal,
+    token_amount: Decimal,
+    clearing_price: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+struct RadixMemeFairLaunchFailedEvent {
+    token_address: ResourceAddress,
+    xrd_amount: Decimal,
+    fair_launch_min_xrd: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+struct RadixMemeRefundEvent {
+    token_address: ResourceAddress,
+    xrd_refunded: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+struct RadixMemeTokenGraduatedEvent {
+    token_address: ResourceAddress,
+    component_address: ComponentAddress,
+    xrd_raised: Decimal,
+    listing_fee: Decimal,
+    creator_fee: Decimal,
+    xrd_remaining: Decimal,
+    xrd_to_parent: Decimal,
+    dex_adapter: Option<ComponentAddress>,
+    pool_address: Option<ComponentAddress>,
+    pool_token_amount: Decimal,
+    final_supply: Decimal,
+    final_price: Decimal,
+    time_graduated: i64,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+struct RadixMemeLpTokensEvent {
+    token_address: ResourceAddress,
+    pool_address: ComponentAddress,
+    lp_lock_address: ComponentAddress,
+    lp_resource: ResourceAddress,
+    lp_amount: Decimal,
+    lp_token_policy: LpTokenPolicy,
+    unlock_time: Option<i64>,
+}
+
 #[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
 struct RadixMemeClaimTokensEvent {
     tokens_claimed: Decimal,
@@ -39,11 +138,32 @@ struct RadixMemeClaimFeeEvent {
     fee_claimed: Decimal,
 }
 
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+struct RadixMemeForwardFeeEvent {
+    token_address: ResourceAddress,
+    fee_forwarded: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+struct RadixMemeClaimCreatorFeeEvent {
+    token_address: ResourceAddress,
+    fee_claimed: Decimal,
+}
+
 #[blueprint]
 #[events(
     RadixMemeTokenCreateEvent,
+    RadixMemeStatusChangeEvent,
     RadixMemeTokenTradeEvent,
-    RadixMemeClaimFeeEvent
+    RadixMemeFairLaunchClearedEvent,
+    RadixMemeFairLaunchFailedEvent,
+    RadixMemeRefundEvent,
+    RadixMemeClaimTokensEvent,
+    RadixMemeTokenGraduatedEvent,
+    RadixMemeLpTokensEvent,
+    RadixMemeClaimFeeEvent,
+    RadixMemeForwardFeeEvent,
+    RadixMemeClaimCreatorFeeEvent
 )]
 mod radix_meme_token_curve {
 
@@ -69,14 +189,38 @@ mod radix_meme_token_curve {
         roles {
             creator => updatable_by: [OWNER];
             radix_meme_admin => updatable_by: [radix_meme_admin];
+            radix_meme_parent => updatable_by: [];
         },
         methods {
             buy => PUBLIC;
             buy_amount => PUBLIC;
             sell => PUBLIC;
             sell_for_xrd_amount => PUBLIC;
+            quote_buy => PUBLIC;
+            quote_buy_amount => PUBLIC;
+            quote_sell => PUBLIC;
+            quote_sell_for_xrd => PUBLIC;
             claim_fair_launch_tokens => PUBLIC;
-            claim_all_fees => restrict_to: [radix_meme_admin];
+            refund_fair_launch => PUBLIC;
+            claim_all_fees => restrict_to: [radix_meme_admin, radix_meme_parent];
+            buy_with_pass => PUBLIC;
+            buy_amount_with_pass => PUBLIC;
+            sell_with_pass => PUBLIC;
+            sell_for_xrd_amount_with_pass => PUBLIC;
+            buy_with_referrer => PUBLIC;
+            buy_with_badge => PUBLIC;
+            buy_amount_with_referrer => PUBLIC;
+            sell_with_referrer => PUBLIC;
+            sell_for_xrd_amount_with_referrer => PUBLIC;
+            claim_creator_fees => restrict_to: [creator];
+            set_fee_forward_threshold => restrict_to: [radix_meme_admin];
+            get_lp_token_info => PUBLIC;
+            get_status => PUBLIC;
+            refresh_status => PUBLIC;
+            graduated_pool => PUBLIC;
+            pause => restrict_to: [radix_meme_admin];
+            unpause => restrict_to: [radix_meme_admin];
+            wind_down => restrict_to: [radix_meme_admin];
         }
     }
     struct RadixMemeTokenCurve {
@@ -90,21 +234,40 @@ mod radix_meme_token_curve {
         pub max_xrd_market_cap: Decimal, // the maximum market cap in XRD that will be reached when the max tokens have been traded on bonding curve
         pub max_xrd: Decimal, // the maximum XRD that will be received into this component
         pub tx_fee_perc: Decimal, // fee % taken on every tx, specified in decimals 1% = 0.01,
+        pub fee_schedule: Option<FeeSchedule>, // optional launch fee schedule that replaces the tx_fee_perc while it applies
         pub listing_fee_perc: Decimal, // fee % taken when a token is listed on external dex, specified in decimals 1% = 0.01
         pub creator_fee_perc: Decimal, // fee % paid to the token creator when the token is listed on a dex, specified in decimals 1% = 0.01
-        pub multiplier: PreciseDecimal, // the constant multiplier that is used in the bonding curve calcs. This is based on the max_supply and max_xrd values.
-        pub xrd_vault: Vault,           // the vault that holds all the XRD recived by the component
-        pub fee_vault: Vault,           // vault that holds all the fees earned by the component
-        pub creator_fee_vault: Vault,   // vault that holds fees earned by the creator of the token
-        pub last_price: Decimal,        // the price reached with the last trade on the component
+        pub creator_fee_share: Decimal, // share of every tx fee paid to the token creator, specified in decimals 50% = 0.5
+        pub fee_forward_threshold: Option<Decimal>, // fees are forwarded to the parent component once the fee_vault reaches this amount (0 = every trade, None = never)
+        pub pool_fee_perc: Decimal, // swap fee % of the pool created when the token graduates, specified in decimals 1% = 0.01
+        pub radix_meme_admin_rule: AccessRule, // the access rule of the radix.meme admins, used as the admin rule of the pool created at graduation
+        pub dex_adapter: Option<ComponentAddress>, // the DEX adapter used to list the token when it graduates (None = the built-in RadixMemePool)
+        pub lp_token_policy: LpTokenPolicy, // whether the LP tokens received when the token graduates are locked forever or time locked
+        pub route_graduated_trades: bool, // whether buys and sells are routed through the built-in pool after the token graduates, instead of being rejected. Routed trades only pay the pool fee (no tx fee, pass discount or referral share). Exact amount trades (buy_amount, sell_for_xrd_amount) are always rejected after graduation
+        pub bonding_curve: BondingCurveKind, // the bonding curve (and its parameters) used for all price calcs on this component
+        pub xrd_vault: Vault, // the vault that holds all the XRD recived by the component
+        pub fee_vault: Vault, // vault that holds all the fees earned by the component
+        pub creator_fee_vault: Vault, // vault that holds fees earned by the creator of the token
+        pub last_price: Decimal, // the price reached with the last trade on the component
         pub current_supply: Decimal, // the current supply of the token associated with this component
         pub fair_launch_period_mins: u32, // the number of mins allocated for a fair launch period
-        pub in_fair_launch_period: bool, // indicates whether the token is still in its fair_launch_period
         pub fair_launch_receipt_manager: ResourceManager, // teh resource manager for fair launch receipts
-        pub fair_launch_tokens: Vault, // vault containing tokens that are bought during fair launch period
+        pub fair_launch_tokens: Vault, // vault containing the tokens minted at the clearing price when the fair launch period ends
         pub fair_launch_xrd: Decimal, // amount of xrd corresponding to tokens in fair launch tokens vault - used to determine tokens that cna be claimed
+        pub fair_launch_price: Decimal, // the clearing price at which all fair launch receipts are redeemed
+        pub fair_launch_min_xrd: Decimal, // the min XRD the fair launch must raise. Below it, fair launch receipts are refunded instead of claimed
+        pub fair_launch_max_xrd: Option<Decimal>, // the max XRD a participant can spend during the fair launch period (None = no cap)
+        pub fair_launch_badge_resource: Option<ResourceAddress>, // the badge that identifies fair launch participants for the cap (None = the cap applies per buy)
+        pub fair_launch_participants: KeyValueStore<NonFungibleLocalId, Decimal>, // the XRD spent during the fair launch by each badge holder
         pub time_created: i64, // the date the token curve was created in seconds since unix epoch - included for easy lookup
+        pub start_time: i64, // the date trading starts in seconds since unix epoch. The fair launch period and fee schedule start at this time
         pub target_reached: i64, // the date the token reached its target market cap in seconds since unix epoch
+        pub status: CurveStatus, // the lifecycle status of the token curve
+        pub paused_status: Option<CurveStatus>, // the status the token curve returns to when it is unpaused
+        pub pool_address: Option<ComponentAddress>, // the address of the pool the token was listed on when it graduated
+        pub lp_lock_address: Option<ComponentAddress>, // the RadixMemeLpLock component that holds the LP tokens received for the liquidity added to the pool at graduation
+        pub lp_resource: Option<ResourceAddress>, // the LP token resource of the pool the token was listed on
+        pub lp_unlock_time: Option<i64>, // the time time locked LP tokens can be withdrawn in seconds since unix epoch
     }
 
     impl RadixMemeTokenCurve {
@@ -123,9 +286,21 @@ mod radix_meme_token_curve {
             max_token_supply_to_trade: Decimal,
             max_xrd_market_cap: Decimal,
             tx_fee_perc: Decimal,
+            fee_schedule: Option<FeeSchedule>,
             listing_fee_perc: Decimal,
             creator_fee_perc: Decimal,
+            creator_fee_share: Decimal,
+            fee_forward_threshold: Option<Decimal>,
+            pool_fee_perc: Decimal,
+            dex_adapter: Option<ComponentAddress>,
+            lp_token_policy: LpTokenPolicy,
+            route_graduated_trades: bool,
+            bonding_curve_config: BondingCurveConfig,
+            start_time: Option<i64>,
             fair_launch_period_mins: u32,
+            fair_launch_min_xrd: Decimal,
+            fair_launch_max_xrd: Option<Decimal>,
+            fair_launch_badge_resource: Option<ResourceAddress>,
             parent_address: ComponentAddress,
             parent_owner_rule: AccessRule,
         ) -> (
@@ -133,8 +308,16 @@ mod radix_meme_token_curve {
             NonFungibleBucket,
             ComponentAddress,
         ) {
+            assert!(
+                max_token_supply > max_token_supply_to_trade,
+                "max_token_supply must be more than max_token_supply_to_trade, so that there are tokens left to list on a pool when the token graduates."
+            );
             assert!(tx_fee_perc < Decimal::ONE, "tx_fee_perc cannot be >= 1. tx_fee_perc is specified in decimals, e.g. 1% = 0.01. ");
+            if let Some(schedule) = fee_schedule.as_ref() {
+                schedule.validate();
+            }
             assert!(listing_fee_perc < Decimal::ONE, "listing_fee_perc cannot be >= 1. listing_fee_perc is specified in decimals, e.g. 1% = 0.01. ");
+            assert!(creator_fee_share >= Decimal::ZERO && creator_fee_share <= Decimal::ONE, "creator_fee_share must be between 0 and 1. creator_fee_share is specified in decimals, e.g. 50% = 0.5. ");
             let _parent_instance = Global::<RadixMemeMain>::from(parent_address.clone()); // checks that the function was called from a TokenCurves component
                                                                                           // let require_parent = rule!(require(global_caller(parent_address.clone())));
             let (address_reservation, component_address) =
@@ -242,13 +425,35 @@ mod radix_meme_token_curve {
             dapp_def_account.set_owner_role(rule!(require(owner_badge.resource_address())));
             let dapp_def_address = GlobalAddress::from(dapp_def_account.address());
 
-            let multiplier = RadixMemeTokenCurve::calculate_multiplier(
+            let bonding_curve = BondingCurveKind::new(
+                bonding_curve_config,
                 max_xrd_market_cap.clone(),
                 max_token_supply_to_trade.clone(),
             );
-            let max_xrd = RadixMemeTokenCurve::calculate_max_xrd(
-                multiplier.clone(),
-                max_token_supply_to_trade.clone(),
+            let max_xrd = bonding_curve.calculate_max_xrd(max_token_supply_to_trade.clone());
+            let time_created = Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
+            // without a start time, trading starts as soon as the token is created
+            let start_time = start_time.unwrap_or(time_created);
+            assert!(
+                start_time >= time_created,
+                "start_time cannot be in the past."
+            );
+            if let Some(badge_address) = fair_launch_badge_resource.as_ref() {
+                assert!(
+                    !badge_address.is_fungible(),
+                    "The fair launch badge resource must be non-fungible, so that each badge identifies a single participant."
+                );
+            }
+            assert!(
+                fair_launch_min_xrd <= max_xrd,
+                "fair_launch_min_xrd cannot be more than the max XRD the token curve can raise."
+            );
+            // the pool is seeded at the final price on the curve, so the supply that is not traded on the curve must be enough
+            // to match the XRD that is left after the listing and creator fees at that price
+            let pool_xrd = max_xrd * (Decimal::ONE - listing_fee_perc - creator_fee_perc);
+            assert!(
+                pool_xrd <= (max_token_supply - max_token_supply_to_trade) * bonding_curve.calculate_price(max_token_supply_to_trade),
+                "max_token_supply is too low to list the token on a pool at the final price of the bonding curve."
             );
 
             let new_token_curve = RadixMemeTokenCurve {
@@ -263,20 +468,45 @@ mod radix_meme_token_curve {
                 max_xrd_market_cap,
                 max_xrd,
                 tx_fee_perc,
+                fee_schedule,
                 listing_fee_perc,
                 creator_fee_perc,
-                multiplier,
+                creator_fee_share,
+                fee_forward_threshold,
+                pool_fee_perc,
+                radix_meme_admin_rule: parent_owner_rule.clone(),
+                dex_adapter,
+                lp_token_policy,
+                route_graduated_trades,
+                bonding_curve,
                 xrd_vault: Vault::new(XRD),
                 fee_vault: Vault::new(XRD),
                 creator_fee_vault: Vault::new(XRD),
                 last_price: Decimal::ZERO,
                 fair_launch_period_mins,
-                in_fair_launch_period: if fair_launch_period_mins > 0 {true} else {false},
                 fair_launch_receipt_manager,
                 fair_launch_tokens: Vault::new(token_address.clone()),
                 fair_launch_xrd: Decimal::ZERO,
-                time_created: Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch,
+                fair_launch_price: Decimal::ZERO,
+                fair_launch_min_xrd,
+                fair_launch_max_xrd,
+                fair_launch_badge_resource,
+                fair_launch_participants: KeyValueStore::new(),
+                time_created,
+                start_time,
                 target_reached: 0,
+                status: if start_time > time_created {
+                    CurveStatus::Scheduled
+                } else if fair_launch_period_mins > 0 {
+                    CurveStatus::FairLaunch
+                } else {
+                    CurveStatus::Trading
+                },
+                paused_status: None,
+                pool_address: None,
+                lp_lock_address: None,
+                lp_resource: None,
+                lp_unlock_time: None,
             }
             .instantiate()
             .prepare_to_globalize(OwnerRole::Updatable(rule!(require(
@@ -286,6 +516,7 @@ mod radix_meme_token_curve {
             .roles(roles! {
                 creator => rule!(require(owner_badge.resource_address()));
                 radix_meme_admin => parent_owner_rule.clone();
+                radix_meme_parent => rule!(require(global_caller(parent_address.clone())));
             })
             .metadata(metadata! {
                 init {
@@ -301,14 +532,488 @@ mod radix_meme_token_curve {
             Runtime::emit_event(RadixMemeTokenCreateEvent {
                 token_address: token_address.clone(),
                 component_address: component_address.clone(),
+                start_time,
             });
             (new_token_curve, owner_badge, component_address)
         }
 
         // function to buy tokens from the bonding curve using the sent XRD
-        // function takes a bucket with XRD to use to buy new tokens
+        // function takes a bucket with XRD to use to buy new tokens and optionally the minimum amount of tokens that must be received
+        // as well as an optional deadline (seconds since unix epoch) after which the trade will be rejected
+        // min_tokens_out must be None during the fair launch period, since the tokens are only allocated when the fair launch clears
         // function returns a bucket with the bought tokens as well as a bucket with any remaining XRD (if any)
-        pub fn buy(&mut self, mut in_bucket: Bucket) -> (Bucket, Bucket) {
+        pub fn buy(
+            &mut self,
+            in_bucket: Bucket,
+            min_tokens_out: Option<Decimal>,
+            deadline: Option<i64>,
+        ) -> (Bucket, Bucket) {
+            self.process_buy(
+                in_bucket,
+                min_tokens_out,
+                deadline,
+                Decimal::ZERO,
+                None,
+                None,
+            )
+        }
+
+        // same as the buy method, but takes in a proof of the radix.meme pass resource to receive a discount on the tx fee
+        // a proof of the fair launch badge can be added to buy with a pass during a fair launch that requires a badge
+        pub fn buy_with_pass(
+            &mut self,
+            pass_proof: Proof,
+            badge_proof: Option<Proof>,
+            in_bucket: Bucket,
+            min_tokens_out: Option<Decimal>,
+            deadline: Option<i64>,
+        ) -> (Bucket, Bucket) {
+            let fee_discount = self.pass_fee_discount(pass_proof);
+            let participant = badge_proof.map(|proof| self.fair_launch_participant(proof));
+            self.process_buy(
+                in_bucket,
+                min_tokens_out,
+                deadline,
+                fee_discount,
+                None,
+                participant,
+            )
+        }
+
+        // same as the buy method, but pays a share of the tx fee to the specified referrer registered on the radix.meme main component
+        // a proof of the fair launch badge can be added to buy with a referrer during a fair launch that requires a badge
+        pub fn buy_with_referrer(
+            &mut self,
+            referrer: NonFungibleLocalId,
+            badge_proof: Option<Proof>,
+            in_bucket: Bucket,
+            min_tokens_out: Option<Decimal>,
+            deadline: Option<i64>,
+        ) -> (Bucket, Bucket) {
+            let participant = badge_proof.map(|proof| self.fair_launch_participant(proof));
+            self.process_buy(
+                in_bucket,
+                min_tokens_out,
+                deadline,
+                Decimal::ZERO,
+                Some(referrer),
+                participant,
+            )
+        }
+
+        // same as the buy method, but takes in a proof of the fair launch badge to identify the participant for the fair launch cap
+        pub fn buy_with_badge(
+            &mut self,
+            badge_proof: Proof,
+            in_bucket: Bucket,
+            min_tokens_out: Option<Decimal>,
+            deadline: Option<i64>,
+        ) -> (Bucket, Bucket) {
+            let participant = self.fair_launch_participant(badge_proof);
+            self.process_buy(
+                in_bucket,
+                min_tokens_out,
+                deadline,
+                Decimal::ZERO,
+                None,
+                Some(participant),
+            )
+        }
+
+        // function to buy a specificly specified amount of tokens
+        // the function takes in the specified value of tokens that must be bought as well as a bucket of XRD to pay for the tx
+        // and optionally the maximum amount of XRD (including fees) that may be spent and a deadline after which the trade will be rejected
+        // the function returns a bucket with the bought tokens as well as a bucket with any remaining XRD (if any)
+        pub fn buy_amount(
+            &mut self,
+            amount: Decimal,
+            in_bucket: Bucket,
+            max_xrd_in: Option<Decimal>,
+            deadline: Option<i64>,
+        ) -> (Bucket, Bucket) {
+            self.process_buy_amount(amount, in_bucket, max_xrd_in, deadline, Decimal::ZERO, None)
+        }
+
+        // same as the buy_amount method, but takes in a proof of the radix.meme pass resource to receive a discount on the tx fee
+        pub fn buy_amount_with_pass(
+            &mut self,
+            pass_proof: Proof,
+            amount: Decimal,
+            in_bucket: Bucket,
+            max_xrd_in: Option<Decimal>,
+            deadline: Option<i64>,
+        ) -> (Bucket, Bucket) {
+            let fee_discount = self.pass_fee_discount(pass_proof);
+            self.process_buy_amount(amount, in_bucket, max_xrd_in, deadline, fee_discount, None)
+        }
+
+        // same as the buy_amount method, but pays a share of the tx fee to the specified referrer registered on the radix.meme main component
+        pub fn buy_amount_with_referrer(
+            &mut self,
+            referrer: NonFungibleLocalId,
+            amount: Decimal,
+            in_bucket: Bucket,
+            max_xrd_in: Option<Decimal>,
+            deadline: Option<i64>,
+        ) -> (Bucket, Bucket) {
+            self.process_buy_amount(
+                amount,
+                in_bucket,
+                max_xrd_in,
+                deadline,
+                Decimal::ZERO,
+                Some(referrer),
+            )
+        }
+
+        // function to sell the tokens provided
+        // function takes in a bucket of tokens to sell and optionally the minimum amount of XRD (after fees) that must be received
+        // as well as an optional deadline (seconds since unix epoch) after which the trade will be rejected
+        // function returns a bucket of XRD from the sale as well as a bucket with any remaining tokens (if any)
+        pub fn sell(
+            &mut self,
+            in_bucket: Bucket,
+            min_xrd_out: Option<Decimal>,
+            deadline: Option<i64>,
+        ) -> (Bucket, Bucket) {
+            self.process_sell(in_bucket, min_xrd_out, deadline, Decimal::ZERO, None)
+        }
+
+        // same as the sell method, but takes in a proof of the radix.meme pass resource to receive a discount on the tx fee
+        pub fn sell_with_pass(
+            &mut self,
+            pass_proof: Proof,
+            in_bucket: Bucket,
+            min_xrd_out: Option<Decimal>,
+            deadline: Option<i64>,
+        ) -> (Bucket, Bucket) {
+            let fee_discount = self.pass_fee_discount(pass_proof);
+            self.process_sell(in_bucket, min_xrd_out, deadline, fee_discount, None)
+        }
+
+        // same as the sell method, but pays a share of the tx fee to the specified referrer registered on the radix.meme main component
+        pub fn sell_with_referrer(
+            &mut self,
+            referrer: NonFungibleLocalId,
+            in_bucket: Bucket,
+            min_xrd_out: Option<Decimal>,
+            deadline: Option<i64>,
+        ) -> (Bucket, Bucket) {
+            self.process_sell(
+                in_bucket,
+                min_xrd_out,
+                deadline,
+                Decimal::ZERO,
+                Some(referrer),
+            )
+        }
+
+        // function to sell tokens to the value of the specified XRD amount
+        // the function takes in the amount of XRD to receive as well as a bucket of tokens to sell
+        // and optionally the maximum amount of tokens that may be sold and a deadline after which the trade will be rejected
+        // the function returns a bucket with XRD and a bucket with any remaining tokens (if any)
+        pub fn sell_for_xrd_amount(
+            &mut self,
+            amount: Decimal,
+            in_bucket: Bucket,
+            max_tokens_in: Option<Decimal>,
+            deadline: Option<i64>,
+        ) -> (Bucket, Bucket) {
+            self.process_sell_for_xrd_amount(
+                amount,
+                in_bucket,
+                max_tokens_in,
+                deadline,
+                Decimal::ZERO,
+                None,
+            )
+        }
+
+        // same as the sell_for_xrd_amount method, but takes in a proof of the radix.meme pass resource to receive a discount on the tx fee
+        pub fn sell_for_xrd_amount_with_pass(
+            &mut self,
+            pass_proof: Proof,
+            amount: Decimal,
+            in_bucket: Bucket,
+            max_tokens_in: Option<Decimal>,
+            deadline: Option<i64>,
+        ) -> (Bucket, Bucket) {
+            let fee_discount = self.pass_fee_discount(pass_proof);
+            self.process_sell_for_xrd_amount(
+                amount,
+                in_bucket,
+                max_tokens_in,
+                deadline,
+                fee_discount,
+                None,
+            )
+        }
+
+        // same as the sell_for_xrd_amount method, but pays a share of the tx fee to the specified referrer registered on the radix.meme main component
+        pub fn sell_for_xrd_amount_with_referrer(
+            &mut self,
+            referrer: NonFungibleLocalId,
+            amount: Decimal,
+            in_bucket: Bucket,
+            max_tokens_in: Option<Decimal>,
+            deadline: Option<i64>,
+        ) -> (Bucket, Bucket) {
+            self.process_sell_for_xrd_amount(
+                amount,
+                in_bucket,
+                max_tokens_in,
+                deadline,
+                Decimal::ZERO,
+                Some(referrer),
+            )
+        }
+
+        // read only method that returns the result of a buy with the specified amount of XRD, as it would be executed by the buy method
+        // once the token has graduated and trades are routed, the quote is for the swap on the pool (see quote_routed_swap)
+        pub fn quote_buy(&self, xrd_amount: Decimal) -> TradeQuote {
+            if let Some(pool) = self.routed_pool() {
+                return Self::quote_routed_swap(pool, XRD, xrd_amount);
+            }
+            self.check_not_graduated();
+            Self::check_status(self.current_status(), &BUY_STATUSES, "buy");
+            self.calculate_buy(xrd_amount, Decimal::ZERO)
+        }
+
+        // read only method that returns the result of buying the specified amount of tokens, as it would be executed by the buy_amount method
+        pub fn quote_buy_amount(&self, amount: Decimal) -> TradeQuote {
+            self.check_exact_amount_not_routed();
+            self.check_not_graduated();
+            Self::check_status(self.current_status(), &BUY_STATUSES, "buy");
+            self.calculate_buy_amount(amount, Decimal::ZERO)
+        }
+
+        // read only method that returns the result of selling the specified amount of tokens, as it would be executed by the sell method
+        // once the token has graduated and trades are routed, the quote is for the swap on the pool (see quote_routed_swap)
+        pub fn quote_sell(&self, token_amount: Decimal) -> TradeQuote {
+            if let Some(pool) = self.routed_pool() {
+                return Self::quote_routed_swap(pool, self.token_manager.address(), token_amount);
+            }
+            self.check_not_graduated();
+            Self::check_status(self.current_status(), &SELL_STATUSES, "sell");
+            self.calculate_sell(token_amount, Decimal::ZERO)
+        }
+
+        // read only method that returns the result of selling tokens for the specified amount of XRD, as it would be executed by the sell_for_xrd_amount method
+        pub fn quote_sell_for_xrd(&self, xrd_amount: Decimal) -> TradeQuote {
+            self.check_exact_amount_not_routed();
+            self.check_not_graduated();
+            Self::check_status(self.current_status(), &SELL_STATUSES, "sell");
+            self.calculate_sell_for_xrd_amount(xrd_amount, Decimal::ZERO)
+        }
+
+        // function to claim tokens allocated during fair launch period
+        pub fn claim_fair_launch_tokens(&mut self, receipts_bucket: Bucket) -> Bucket {
+            let mut out_bucket = Bucket::new(self.token_manager.address());
+            let mut total_xrd = Decimal::ZERO;
+            self.update_status();
+            assert!(self.status != CurveStatus::FairLaunch, "Fair launch period not finished. Fair launch tokens can only be claimed once fair launch period has finished.");
+            Self::check_status(self.status, &CLAIM_STATUSES, "claim fair launch tokens");
+            assert!(
+                receipts_bucket.resource_address() == self.fair_launch_receipt_manager.address(),
+                "Incorrect tokens submitted for claim."
+            );
+            for receipt in receipts_bucket
+                .as_non_fungible()
+                .non_fungibles::<FairLaunchReceiptData>()
+            {
+                let receipt_data = receipt.data();
+                total_xrd = total_xrd + receipt_data.xrd_amount;
+                let mut claim_tokens = self.fair_launch_tokens.amount() * receipt_data.xrd_amount
+                    / self.fair_launch_xrd;
+                if claim_tokens > self.fair_launch_tokens.amount() {
+                    claim_tokens = self.fair_launch_tokens.amount();
+                }
+                out_bucket.put(self.fair_launch_tokens.take(claim_tokens));
+                self.fair_launch_xrd = self.fair_launch_xrd - receipt_data.xrd_amount;
+            }
+            receipts_bucket.burn();
+            Runtime::emit_event(RadixMemeClaimTokensEvent {
+                tokens_claimed: out_bucket.amount(),
+                xrd_amount: total_xrd.clone(),
+            });
+            out_bucket
+        }
+
+        pub fn claim_all_fees(&mut self) -> Bucket {
+            Self::check_status(self.status, &FEE_STATUSES, "claim fees");
+            Runtime::emit_event(RadixMemeClaimFeeEvent {
+                fee_claimed: self.fee_vault.amount(),
+            });
+            self.fee_vault.take_all()
+        }
+
+        // method for the radix.meme admin to change when fees are forwarded to the parent component
+        pub fn set_fee_forward_threshold(&mut self, fee_forward_threshold: Option<Decimal>) {
+            Self::check_status(
+                self.status,
+                &FEE_STATUSES,
+                "change the fee forward threshold",
+            );
+            if let Some(threshold) = fee_forward_threshold {
+                assert!(
+                    threshold >= Decimal::ZERO,
+                    "fee_forward_threshold cannot be negative."
+                );
+            }
+            self.fee_forward_threshold = fee_forward_threshold;
+            self.forward_fees();
+        }
+
+        // method for the token creator to withdraw the creator share of the tx fees earned on the bonding curve
+        pub fn claim_creator_fees(&mut self) -> Bucket {
+            Self::check_status(self.status, &FEE_STATUSES, "claim creator fees");
+            Runtime::emit_event(RadixMemeClaimCreatorFeeEvent {
+                token_address: self.token_manager.address(),
+                fee_claimed: self.creator_fee_vault.amount(),
+            });
+            self.creator_fee_vault.take_all()
+        }
+
+        // read only method that returns where the LP tokens received when the token graduated went
+        pub fn get_lp_token_info(&self) -> LpTokenInfo {
+            let lp_lock = self
+                .lp_lock_address
+                .clone()
+                .map(|lp_lock_address| Global::<RadixMemeLpLock>::from(lp_lock_address));
+            LpTokenInfo {
+                pool_address: self.pool_address.clone(),
+                lp_lock_address: self.lp_lock_address.clone(),
+                lp_resource: self.lp_resource.clone(),
+                lp_amount: lp_lock
+                    .map(|lp_lock| lp_lock.get_lp_amount())
+                    .unwrap_or(Decimal::ZERO),
+                lp_token_policy: self.lp_token_policy.clone(),
+                unlock_time: self.lp_unlock_time.clone(),
+            }
+        }
+
+        // read only method that returns the current lifecycle status of the token curve
+        pub fn get_status(&self) -> CurveStatus {
+            self.current_status()
+        }
+
+        // method that stores time driven status changes (e.g. the end of the fair launch period) and updates the status kept
+        // by the parent component, without having to trade on the token curve
+        pub fn refresh_status(&mut self) -> CurveStatus {
+            self.update_status();
+            self.status
+        }
+
+        // read only method that returns the pool a graduated token was listed on, so wallets and aggregators can find its liquidity
+        // returns None if the token has not graduated or was not listed on a pool
+        pub fn graduated_pool(&self) -> Option<ComponentAddress> {
+            if self.status == CurveStatus::Graduated {
+                self.pool_address.clone()
+            } else {
+                None
+            }
+        }
+
+        // function to refund the XRD of fair launch receipts when the fair launch raised less than the fair launch min XRD
+        // the receipts are burned and the XRD they were bought with (excluding fees) is returned
+        pub fn refund_fair_launch(&mut self, receipts_bucket: Bucket) -> Bucket {
+            self.update_status();
+            Self::check_status(
+                self.status,
+                &[CurveStatus::Refunding],
+                "refund fair launch receipts",
+            );
+            assert!(
+                receipts_bucket.resource_address() == self.fair_launch_receipt_manager.address(),
+                "Incorrect tokens submitted for refund."
+            );
+            let mut total_xrd = Decimal::ZERO;
+            for receipt in receipts_bucket
+                .as_non_fungible()
+                .non_fungibles::<FairLaunchReceiptData>()
+            {
+                total_xrd = total_xrd + receipt.data().xrd_amount;
+            }
+            receipts_bucket.burn();
+            self.fair_launch_xrd = self.fair_launch_xrd - total_xrd;
+            let out_bucket = self.xrd_vault.take(total_xrd);
+            Runtime::emit_event(RadixMemeRefundEvent {
+                token_address: self.token_manager.address(),
+                xrd_refunded: total_xrd,
+            });
+            out_bucket
+        }
+
+        // method for the radix.meme admins to pause all trading on the token curve
+        pub fn pause(&mut self) {
+            self.update_status();
+            Self::check_status(
+                self.status,
+                &[
+                    CurveStatus::Scheduled,
+                    CurveStatus::FairLaunch,
+                    CurveStatus::Trading,
+                ],
+                "pause",
+            );
+            self.paused_status = Some(self.status);
+            self.set_status(CurveStatus::Paused);
+        }
+
+        // method for the radix.meme admins to resume trading on a paused token curve
+        pub fn unpause(&mut self) {
+            Self::check_status(self.status, &[CurveStatus::Paused], "unpause");
+            let paused_status = self
+                .paused_status
+                .take()
+                .expect("No status to return to after pause.");
+            self.set_status(paused_status);
+            self.update_status();
+        }
+
+        // method for the radix.meme admins to wind down a token curve that will not graduate
+        // a wound down token can no longer be bought, but holders can still sell their tokens and claim their fair launch tokens
+        // winding down a fair launch that raised less than the fair launch min XRD moves it to refunding, like a failed fair launch
+        pub fn wind_down(&mut self) {
+            self.update_status();
+            Self::check_status(
+                self.status,
+                &[
+                    CurveStatus::Scheduled,
+                    CurveStatus::FairLaunch,
+                    CurveStatus::Trading,
+                    CurveStatus::Paused,
+                ],
+                "wind down",
+            );
+            // a fair launch that is wound down clears early, so that its receipts can still be claimed
+            // if it raised less than the fair launch min XRD it fails instead, so that its receipts can be refunded
+            if self.status == CurveStatus::FairLaunch
+                || self.paused_status == Some(CurveStatus::FairLaunch)
+            {
+                self.paused_status = None;
+                if self.fair_launch_xrd < self.fair_launch_min_xrd {
+                    self.fail_fair_launch();
+                    return;
+                }
+                self.clear_fair_launch();
+            }
+            self.paused_status = None;
+            self.set_status(CurveStatus::WoundDown);
+        }
+
+        // the following functions process each type of trade for the public trade methods, with the fee discount and referrer (if any) applied
+
+        fn process_buy(
+            &mut self,
+            mut in_bucket: Bucket,
+            min_tokens_out: Option<Decimal>,
+            deadline: Option<i64>,
+            fee_discount: Decimal,
+            referrer: Option<NonFungibleLocalId>,
+            participant: Option<NonFungibleLocalId>,
+        ) -> (Bucket, Bucket) {
+            Self::check_deadline(deadline);
             assert!(
                 Runtime::get_tip_percentage() == 0,
                 "Radix.meme does not allow adding tips to transactions."
@@ -317,78 +1022,44 @@ mod radix_meme_token_curve {
                 in_bucket.resource_address() == XRD,
                 "Can only buy tokens with XRD"
             );
-            self.check_in_fair_launch_period();
-            let mut xrd_amount = in_bucket.amount();
-            info!("XRD amount before fees: {}", xrd_amount);
-            info!("Max xrd: {}", self.max_xrd);
-            let available_xrd = self.max_xrd - self.xrd_vault.amount();
-            let mut fee_amount = xrd_amount * self.tx_fee_perc;
-            if xrd_amount > available_xrd {
-                // calculate fee based on available xrd only
-                fee_amount = available_xrd * self.tx_fee_perc;
-                xrd_amount = xrd_amount - fee_amount;
-                if xrd_amount >= available_xrd {
-                    xrd_amount = available_xrd;
-                    self.target_reached =
-                        Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
-                }
-            } else {
-                xrd_amount = xrd_amount - fee_amount;
-            };
-            self.fee_vault.put(in_bucket.take(fee_amount));
-            info!("XRD Amount after fees: {}", xrd_amount);
-            let mut out_bucket = if self.in_fair_launch_period {
-                // create bucket with fair launch receipt address
-                Bucket::new(self.fair_launch_receipt_manager.address())
-            } else {
-                Bucket::new(self.token_manager.address())
-            };
-            if xrd_amount > Decimal::ZERO {
-                let receive_tokens = RadixMemeTokenCurve::calculate_tokens_received(
-                    xrd_amount.clone(),
-                    self.current_supply.clone(),
-                    self.multiplier.clone(),
+            if let Some(pool) = self.routed_pool() {
+                return Self::route_to_pool(pool, in_bucket, min_tokens_out);
+            }
+            self.check_not_graduated();
+            self.update_status();
+            Self::check_status(self.status, &BUY_STATUSES, "buy");
+            let quote = self.calculate_buy(in_bucket.amount(), fee_discount);
+            // the tokens for a fair launch buy are only known when the fair launch clears, so a min_tokens_out cannot be checked
+            assert!(
+                self.status != CurveStatus::FairLaunch || min_tokens_out.is_none(),
+                "min_tokens_out cannot be used during the fair launch period. Tokens are allocated at the clearing price when the fair launch period ends."
+            );
+            if let Some(min_tokens) = min_tokens_out {
+                assert!(
+                    quote.token_amount >= min_tokens,
+                    "Slippage limit exceeded. Tokens received ({}) less than min_tokens_out ({}).",
+                    quote.token_amount,
+                    min_tokens
                 );
-                if receive_tokens + self.current_supply > self.max_token_supply_to_trade {
-                    panic!("Unexpected error! Not enough tokens remaining for tx.")
-                }
-                let new_tokens = self.token_manager.mint(receive_tokens.clone());
-                let new_tokens_amount = new_tokens.amount();
-                if self.in_fair_launch_period {
-                    // in fair launch period buyer receives a receipt that can be used to claim tokens after the fair launch period.
-                    self.fair_launch_tokens.put(new_tokens);
-                    self.fair_launch_xrd = self.fair_launch_xrd + xrd_amount;
-                    out_bucket.put(self.fair_launch_receipt_manager.mint_ruid_non_fungible(
-                        FairLaunchReceiptData {
-                            xrd_amount: xrd_amount.clone(),
-                        },
-                    ))
-                } else {
-                    out_bucket.put(new_tokens);
-                }
-                self.current_supply = self.current_supply + receive_tokens.clone();
-                self.xrd_vault.put(in_bucket.take(xrd_amount));
-                self.last_price =
-                    RadixMemeTokenCurve::calculate_price(&self.current_supply, &self.multiplier);
-                Runtime::emit_event(RadixMemeTokenTradeEvent {
-                    token_address: self.token_manager.address(),
-                    side: String::from("buy"),
-                    fair_launch_period: self.in_fair_launch_period.clone(),
-                    token_amount: new_tokens_amount.clone(),
-                    xrd_amount: xrd_amount.clone(),
-                    end_price: self.last_price.clone(),
-                });
             }
-            if self.target_reached > 0 {
-                self.list_token();
+            if self.status == CurveStatus::FairLaunch {
+                self.record_fair_launch_participation(participant, quote.xrd_amount);
             }
+            self.deposit_fees(in_bucket.take(quote.fee_amount), referrer);
+            let out_bucket = self.execute_buy(&quote, in_bucket.take(quote.xrd_amount));
             (out_bucket, in_bucket)
         }
 
-        // function to buy a specificly specified amount of tokens
-        // the function takes in the specified value of tokens that must be bought as well as a bucket of XRD to pay for the tx
-        // the function returns a bucket with the bought tokens as well as a bucket with any remaining XRD (if any)
-        pub fn buy_amount(&mut self, amount: Decimal, mut in_bucket: Bucket) -> (Bucket, Bucket) {
+        fn process_buy_amount(
+            &mut self,
+            amount: Decimal,
+            mut in_bucket: Bucket,
+            max_xrd_in: Option<Decimal>,
+            deadline: Option<i64>,
+            fee_discount: Decimal,
+            referrer: Option<NonFungibleLocalId>,
+        ) -> (Bucket, Bucket) {
+            Self::check_deadline(deadline);
             assert!(
                 Runtime::get_tip_percentage() == 0,
                 "Radix.meme does not allow adding tips to transactions."
@@ -397,68 +1068,36 @@ mod radix_meme_token_curve {
                 in_bucket.resource_address() == XRD,
                 "Can only buy tokens with XRD"
             );
-            assert!(
-                amount + self.current_supply <= self.max_token_supply_to_trade,
-                "Cannot buy requested amount of tokens. Not enough supply left"
-            );
-            self.check_in_fair_launch_period();
-            let mut out_bucket = if self.in_fair_launch_period {
-                Bucket::new(self.fair_launch_receipt_manager.address())
-            } else {
-                Bucket::new(self.token_manager.address())
-            };
-            if amount > Decimal::ZERO {
-                let xrd_required = RadixMemeTokenCurve::calculate_buy_price(
-                    amount.clone(),
-                    self.current_supply.clone(),
-                    self.multiplier.clone(),
+            self.check_exact_amount_not_routed();
+            self.check_not_graduated();
+            self.update_status();
+            Self::check_status(self.status, &BUY_STATUSES, "buy");
+            let quote = self.calculate_buy_amount(amount, fee_discount);
+            if let Some(max_xrd) = max_xrd_in {
+                assert!(
+                    quote.xrd_amount + quote.fee_amount <= max_xrd,
+                    "Slippage limit exceeded. XRD required ({}) more than max_xrd_in ({}).",
+                    quote.xrd_amount + quote.fee_amount,
+                    max_xrd
                 );
-                let fee_amount = xrd_required * self.tx_fee_perc;
-                if xrd_required + fee_amount > in_bucket.amount() {
-                    panic!("Not enough XRD sent for tx.");
-                }
-                if xrd_required + self.xrd_vault.amount() > self.max_xrd {
-                    panic!("Unexpected error! Max XRD will be exceeded in tx.")
-                }
-                self.fee_vault.put(in_bucket.take(fee_amount));
-                let new_tokens = self.token_manager.mint(amount.clone());
-                let new_tokens_amount = new_tokens.amount();
-                if self.in_fair_launch_period {
-                    self.fair_launch_tokens.put(new_tokens);
-                    self.fair_launch_xrd = self.fair_launch_xrd + xrd_required;
-                    out_bucket.put(self.fair_launch_receipt_manager.mint_ruid_non_fungible(
-                        FairLaunchReceiptData {
-                            xrd_amount: xrd_required.clone(),
-                        },
-                    ))
-                } else {
-                    out_bucket.put(new_tokens);
-                }
-                self.current_supply = self.current_supply + amount;
-                self.xrd_vault.put(in_bucket.take(xrd_required));
-                self.last_price =
-                    RadixMemeTokenCurve::calculate_price(&self.current_supply, &self.multiplier);
-                if self.xrd_vault.amount() >= self.max_xrd {
-                    self.target_reached =
-                        Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
-                    self.list_token();
-                }
-                Runtime::emit_event(RadixMemeTokenTradeEvent {
-                    token_address: self.token_manager.address(),
-                    side: String::from("buy"),
-                    fair_launch_period: self.in_fair_launch_period.clone(),
-                    token_amount: new_tokens_amount.clone(),
-                    xrd_amount: xrd_required.clone(),
-                    end_price: self.last_price.clone(),
-                });
             }
+            if quote.xrd_amount + quote.fee_amount > in_bucket.amount() {
+                panic!("Not enough XRD sent for tx.");
+            }
+            self.deposit_fees(in_bucket.take(quote.fee_amount), referrer);
+            let out_bucket = self.execute_buy(&quote, in_bucket.take(quote.xrd_amount));
             (out_bucket, in_bucket)
         }
 
-        // function to sell the tokens provided
-        // function takes in a bucket of tokens to sell
-        // function returns a bucket of XRD from the sale as well as a bucket with any remaining tokens (if any)
-        pub fn sell(&mut self, mut in_bucket: Bucket) -> (Bucket, Bucket) {
+        fn process_sell(
+            &mut self,
+            in_bucket: Bucket,
+            min_xrd_out: Option<Decimal>,
+            deadline: Option<i64>,
+            fee_discount: Decimal,
+            referrer: Option<NonFungibleLocalId>,
+        ) -> (Bucket, Bucket) {
+            Self::check_deadline(deadline);
             assert!(
                 Runtime::get_tip_percentage() == 0,
                 "Radix.meme does not allow adding tips to transactions."
@@ -467,53 +1106,34 @@ mod radix_meme_token_curve {
                 in_bucket.resource_address() == self.token_manager.address(),
                 "Wrong tokens sent in bucket"
             );
-            self.check_in_fair_launch_period();
-            if self.in_fair_launch_period {
-                panic!("Cannot sell tokens during fair launch period.")
-            }
-            let token_amount = in_bucket.amount();
-            if token_amount > self.current_supply {
-                panic!("Unexpected error! Sending more tokens to sell than current supply.");
+            if let Some(pool) = self.routed_pool() {
+                return Self::route_to_pool(pool, in_bucket, min_xrd_out);
             }
-            let mut out_bucket = Bucket::new(XRD);
-            if token_amount > Decimal::ZERO {
-                let mut receive_xrd = RadixMemeTokenCurve::calculate_sell_price(
-                    token_amount.clone(),
-                    self.current_supply.clone(),
-                    self.multiplier.clone(),
+            self.check_not_graduated();
+            self.update_status();
+            Self::check_status(self.status, &SELL_STATUSES, "sell");
+            let quote = self.calculate_sell(in_bucket.amount(), fee_discount);
+            if let Some(min_xrd) = min_xrd_out {
+                assert!(
+                    quote.xrd_amount >= min_xrd,
+                    "Slippage limit exceeded. XRD received ({}) less than min_xrd_out ({}).",
+                    quote.xrd_amount,
+                    min_xrd
                 );
-                if receive_xrd > self.xrd_vault.amount() {
-                    panic!("Unexpected error! Not enough XRD in component for sell tx.")
-                }
-                let fee_amount = receive_xrd * self.tx_fee_perc;
-                self.fee_vault.put(self.xrd_vault.take(fee_amount));
-                receive_xrd = receive_xrd - fee_amount;
-                let burn_bucket = in_bucket.take(token_amount);
-                burn_bucket.burn();
-                self.current_supply = self.current_supply - token_amount.clone();
-                out_bucket.put(self.xrd_vault.take(receive_xrd.clone()));
-                self.last_price =
-                    RadixMemeTokenCurve::calculate_price(&self.current_supply, &self.multiplier);
-                Runtime::emit_event(RadixMemeTokenTradeEvent {
-                    token_address: self.token_manager.address(),
-                    side: String::from("sell"),
-                    fair_launch_period: self.in_fair_launch_period.clone(),
-                    token_amount: token_amount.clone(),
-                    xrd_amount: out_bucket.amount(),
-                    end_price: self.last_price.clone(),
-                });
             }
-            (out_bucket, in_bucket)
+            self.execute_sell(&quote, in_bucket, referrer)
         }
 
-        // function to sell tokens to the value of the specified XRD amount
-        // the function takes in the amount of XRD to receive as well as a bucket of tokens to sell
-        // the function returns a bucket with XRD and a bucket with any remaining tokens (if any)
-        pub fn sell_for_xrd_amount(
+        fn process_sell_for_xrd_amount(
             &mut self,
             amount: Decimal,
-            mut in_bucket: Bucket,
+            in_bucket: Bucket,
+            max_tokens_in: Option<Decimal>,
+            deadline: Option<i64>,
+            fee_discount: Decimal,
+            referrer: Option<NonFungibleLocalId>,
         ) -> (Bucket, Bucket) {
+            Self::check_deadline(deadline);
             assert!(
                 Runtime::get_tip_percentage() == 0,
                 "Radix.meme does not allow adding tips to transactions."
@@ -522,308 +1142,664 @@ mod radix_meme_token_curve {
                 in_bucket.resource_address() == self.token_manager.address(),
                 "Wrong tokens sent in bucket"
             );
-            self.check_in_fair_launch_period();
-            if self.in_fair_launch_period {
-                panic!("Cannot sell tokens during fair launch period.")
+            self.check_exact_amount_not_routed();
+            self.check_not_graduated();
+            self.update_status();
+            Self::check_status(self.status, &SELL_STATUSES, "sell");
+            let quote = self.calculate_sell_for_xrd_amount(amount, fee_discount);
+            if let Some(max_tokens) = max_tokens_in {
+                assert!(
+                    quote.token_amount <= max_tokens,
+                    "Slippage limit exceeded. Tokens required ({}) more than max_tokens_in ({}).",
+                    quote.token_amount,
+                    max_tokens
+                );
+            }
+            if quote.token_amount > in_bucket.amount() {
+                panic!("Not enough tokens supplied for required amount of XRD");
             }
-            let fee_amount = amount * self.tx_fee_perc;
-            if amount + fee_amount > self.xrd_vault.amount() {
-                panic!("Not enough XRD in component vault for requested amount.");
+            self.execute_sell(&quote, in_bucket, referrer)
+        }
+
+        // the following functions calculate the result of each type of trade without changing any state.
+        // They are used by both the trade methods and the quote methods so that quotes always match execution.
+
+        // the state of the component used by the curve math calculations
+        // during a fair launch no tokens have been minted yet, so the supply is the supply the fair launch would clear at
+        fn curve_state(&self, fee_discount: Decimal) -> CurveState {
+            let mut state = CurveState {
+                current_supply: self.current_supply,
+                xrd_reserve: self.xrd_vault.amount(),
+                max_xrd: self.max_xrd,
+                max_token_supply_to_trade: self.max_token_supply_to_trade,
+                tx_fee_perc: self.current_tx_fee_perc(),
+                fee_discount,
+            };
+            if self.status == CurveStatus::FairLaunch {
+                state.current_supply =
+                    curve_math::fair_launch_clearing(&self.bonding_curve, &state).0;
             }
-            self.fee_vault.put(self.xrd_vault.take(fee_amount));
+            state
+        }
+
+        // checks the pass proof against the pass resource set on the parent component and returns the fee discount for the amount of passes held
+        fn pass_fee_discount(&self, pass_proof: Proof) -> Decimal {
+            let parent_instance = Global::<RadixMemeMain>::from(self.parent_address.clone());
+            let (pass_resource, discount_tiers) = parent_instance.get_pass_fee_discounts();
+            let pass_resource =
+                pass_resource.expect("No pass resource has been set for fee discounts.");
+            let checked_proof = pass_proof
+                .check_with_message(pass_resource, "Incorrect pass resource for fee discount.");
+            curve_math::pass_fee_discount(&discount_tiers, checked_proof.amount())
+        }
+
+        // marks the fair launch as failed. Tokens are only minted when the fair launch clears, so any tokens in the
+        // fair_launch_tokens vault are burned and the pooled XRD stays in the xrd_vault for the refunds
+        fn fail_fair_launch(&mut self) {
+            if !self.fair_launch_tokens.is_empty() {
+                self.fair_launch_tokens.take_all().burn();
+            }
+            Runtime::emit_event(RadixMemeFairLaunchFailedEvent {
+                token_address: self.token_manager.address(),
+                xrd_amount: self.fair_launch_xrd.clone(),
+                fair_launch_min_xrd: self.fair_launch_min_xrd.clone(),
+            });
+            self.set_status(CurveStatus::Refunding);
+        }
+
+        // checks the badge proof against the fair launch badge resource and returns the id of the badge that identifies the participant
+        fn fair_launch_participant(&self, badge_proof: Proof) -> NonFungibleLocalId {
+            let badge_resource = self
+                .fair_launch_badge_resource
+                .expect("No badge resource has been set for the fair launch.");
+            let badge_ids = badge_proof
+                .check_with_message(
+                    badge_resource,
+                    "Incorrect badge resource for the fair launch.",
+                )
+                .as_non_fungible()
+                .non_fungible_local_ids();
+            assert!(
+                badge_ids.len() == 1,
+                "The fair launch badge proof must contain exactly one badge. Badges in proof: {}.",
+                badge_ids.len()
+            );
+            badge_ids.into_iter().next().unwrap()
+        }
+
+        // checks that a fair launch buy stays within the fair launch cap (if any) and records the XRD spent by the participant
+        // if a fair launch badge resource is set, a badge is required and the cap applies to all buys made with the same badge
+        fn record_fair_launch_participation(
+            &mut self,
+            participant: Option<NonFungibleLocalId>,
+            xrd_amount: Decimal,
+        ) {
+            let max_xrd = match self.fair_launch_max_xrd {
+                Some(max_xrd) => max_xrd,
+                None => return,
+            };
+            let participant = match (self.fair_launch_badge_resource, participant) {
+                (Some(_), Some(participant)) => Some(participant),
+                (Some(_), None) => panic!("A fair launch badge is required to buy during the fair launch period. Use buy_with_badge, or add a badge proof to buy_with_pass or buy_with_referrer."),
+                (None, _) => None,
+            };
+            let previous_xrd = participant
+                .as_ref()
+                .and_then(|id| self.fair_launch_participants.get(id).map(|xrd| *xrd))
+                .unwrap_or(Decimal::ZERO);
+            assert!(
+                previous_xrd + xrd_amount <= max_xrd,
+                "Fair launch cap exceeded. Max XRD per participant: {}, XRD already spent: {}, XRD in this buy: {}.",
+                max_xrd,
+                previous_xrd,
+                xrd_amount
+            );
+            if let Some(participant) = participant {
+                self.fair_launch_participants
+                    .insert(participant, previous_xrd + xrd_amount);
+            }
+        }
+
+        // the tx fee % that applies at this moment, based on the fee schedule (if any)
+        fn current_tx_fee_perc(&self) -> Decimal {
+            match self.fee_schedule.as_ref() {
+                Some(schedule) => schedule.fee_perc_at(
+                    Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch
+                        - self.start_time,
+                ),
+                None => self.tx_fee_perc,
+            }
+        }
+
+        // calculates the result of buying tokens with the specified amount of XRD (including fees)
+        fn calculate_buy(&self, xrd_in: Decimal, fee_discount: Decimal) -> TradeQuote {
+            if self.fair_launch_period_active() {
+                curve_math::calculate_fair_launch_buy(
+                    &self.bonding_curve,
+                    &self.curve_state(fee_discount),
+                    xrd_in,
+                )
+            } else {
+                curve_math::calculate_buy(
+                    &self.bonding_curve,
+                    &self.curve_state(fee_discount),
+                    xrd_in,
+                )
+            }
+        }
+
+        // calculates the result of buying the specified amount of tokens
+        fn calculate_buy_amount(&self, amount: Decimal, fee_discount: Decimal) -> TradeQuote {
+            assert!(
+                !self.fair_launch_period_active(),
+                "Cannot buy a specific amount of tokens during the fair launch period. Tokens are allocated at the clearing price when the fair launch period ends."
+            );
+            curve_math::calculate_buy_amount(
+                &self.bonding_curve,
+                &self.curve_state(fee_discount),
+                amount,
+            )
+        }
+
+        // calculates the result of selling the specified amount of tokens. The xrd_amount is the XRD received after fees.
+        fn calculate_sell(&self, token_amount: Decimal, fee_discount: Decimal) -> TradeQuote {
+            curve_math::calculate_sell(
+                &self.bonding_curve,
+                &self.curve_state(fee_discount),
+                token_amount,
+            )
+        }
+
+        // calculates the result of selling tokens to receive the specified amount of XRD after fees
+        fn calculate_sell_for_xrd_amount(
+            &self,
+            amount: Decimal,
+            fee_discount: Decimal,
+        ) -> TradeQuote {
+            curve_math::calculate_sell_for_xrd_amount(
+                &self.bonding_curve,
+                &self.curve_state(fee_discount),
+                amount,
+            )
+        }
+
+        // mints the quoted tokens (or fair launch receipt) for the XRD in the bucket and updates the curve state
+        // returns a bucket with the new tokens or fair launch receipt
+        fn execute_buy(&mut self, quote: &TradeQuote, xrd_bucket: Bucket) -> Bucket {
+            if self.status == CurveStatus::FairLaunch {
+                return self.execute_fair_launch_buy(quote, xrd_bucket);
+            }
+            let mut out_bucket = Bucket::new(self.token_manager.address());
+            self.xrd_vault.put(xrd_bucket);
+            if quote.token_amount > Decimal::ZERO {
+                out_bucket.put(self.token_manager.mint(quote.token_amount.clone()));
+                self.current_supply = self.current_supply + quote.token_amount;
+                self.last_price = quote.end_price.clone();
+                Runtime::emit_event(RadixMemeTokenTradeEvent {
+                    token_address: self.token_manager.address(),
+                    side: String::from("buy"),
+                    fair_launch_period: false,
+                    token_amount: quote.token_amount.clone(),
+                    xrd_amount: quote.xrd_amount.clone(),
+                    fee_perc: quote.fee_perc.clone(),
+                    fee_discount: quote.fee_discount.clone(),
+                    fee_amount: quote.fee_amount.clone(),
+                    end_price: self.last_price.clone(),
+                });
+            }
+            if quote.target_reached {
+                self.target_reached =
+                    Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
+                self.set_status(CurveStatus::Graduating);
+                self.list_token();
+            }
+            out_bucket
+        }
+
+        // pools the XRD of a fair launch buy and returns a fair launch receipt for it
+        // the receipt is redeemed for tokens at the clearing price once the fair launch period ends
+        // a buy that reaches the max market cap ends the fair launch immediately
+        fn execute_fair_launch_buy(&mut self, quote: &TradeQuote, xrd_bucket: Bucket) -> Bucket {
+            let mut out_bucket = Bucket::new(self.fair_launch_receipt_manager.address());
+            self.xrd_vault.put(xrd_bucket);
+            if quote.xrd_amount > Decimal::ZERO {
+                self.fair_launch_xrd = self.fair_launch_xrd + quote.xrd_amount;
+                out_bucket.put(self.fair_launch_receipt_manager.mint_ruid_non_fungible(
+                    FairLaunchReceiptData {
+                        xrd_amount: quote.xrd_amount.clone(),
+                    },
+                ));
+                Runtime::emit_event(RadixMemeTokenTradeEvent {
+                    token_address: self.token_manager.address(),
+                    side: String::from("buy"),
+                    fair_launch_period: true,
+                    token_amount: quote.token_amount.clone(),
+                    xrd_amount: quote.xrd_amount.clone(),
+                    fee_perc: quote.fee_perc.clone(),
+                    fee_discount: quote.fee_discount.clone(),
+                    fee_amount: quote.fee_amount.clone(),
+                    end_price: quote.end_price.clone(),
+                });
+            }
+            if quote.target_reached {
+                self.end_fair_launch();
+            }
+            out_bucket
+        }
+
+        // mints the tokens for all the XRD pooled during the fair launch at a single clearing price
+        // the tokens are held in the fair_launch_tokens vault until they are claimed with the fair launch receipts
+        fn clear_fair_launch(&mut self) {
+            let (clearing_supply, clearing_price) = curve_math::fair_launch_clearing(
+                &self.bonding_curve,
+                &self.curve_state(Decimal::ZERO),
+            );
+            if clearing_supply > Decimal::ZERO {
+                self.fair_launch_tokens
+                    .put(self.token_manager.mint(clearing_supply.clone()));
+            }
+            self.current_supply = clearing_supply.clone();
+            self.last_price = self.bonding_curve.calculate_price(clearing_supply.clone());
+            self.fair_launch_price = clearing_price.clone();
+            Runtime::emit_event(RadixMemeFairLaunchClearedEvent {
+                token_address: self.token_manager.address(),
+                xrd_amount: self.fair_launch_xrd.clone(),
+                token_amount: clearing_supply,
+                clearing_price,
+            });
+        }
+
+        // ends the fair launch by clearing it and starting trading on the curve
+        // the token graduates straight away if the fair launch raised the max market cap
+        // a fair launch that raised less than the fair launch min XRD fails and its receipts can only be refunded
+        fn end_fair_launch(&mut self) {
+            if self.fair_launch_xrd < self.fair_launch_min_xrd {
+                self.fail_fair_launch();
+                return;
+            }
+            self.clear_fair_launch();
+            self.set_status(CurveStatus::Trading);
+            if self.current_supply >= self.max_token_supply_to_trade {
+                self.target_reached =
+                    Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
+                self.set_status(CurveStatus::Graduating);
+                self.list_token();
+            }
+        }
+
+        // burns the quoted tokens from the bucket and pays out the quoted XRD, after taking the fee
+        // returns a bucket with the XRD and a bucket with any remaining tokens
+        fn execute_sell(
+            &mut self,
+            quote: &TradeQuote,
+            mut in_bucket: Bucket,
+            referrer: Option<NonFungibleLocalId>,
+        ) -> (Bucket, Bucket) {
             let mut out_bucket = Bucket::new(XRD);
-            info!("In bucket amount: {:?}", in_bucket.amount());
-            if amount > Decimal::ZERO {
-                let tokens_to_sell = RadixMemeTokenCurve::calculate_tokens_to_sell(
-                    amount.clone() + fee_amount.clone(),
-                    self.current_supply.clone(),
-                    self.multiplier.clone(),
-                );
-                info!("Tokens required: {:?}", tokens_to_sell);
-                if tokens_to_sell > in_bucket.amount() {
-                    panic!("Not enough tokens supplied for required amount of XRD");
-                }
-                if tokens_to_sell > self.current_supply {
-                    panic!("Unexpected error! Not enough token supply in component to sell.");
-                }
-                let burn_bucket = in_bucket.take(tokens_to_sell.clone());
+            if quote.token_amount > Decimal::ZERO {
+                let fee_bucket = self.xrd_vault.take(quote.fee_amount);
+                self.deposit_fees(fee_bucket, referrer);
+                let burn_bucket = in_bucket.take(quote.token_amount.clone());
                 burn_bucket.burn();
-                self.current_supply = self.current_supply - tokens_to_sell;
-                out_bucket.put(self.xrd_vault.take(amount.clone()));
-                self.last_price =
-                    RadixMemeTokenCurve::calculate_price(&self.current_supply, &self.multiplier);
+                self.current_supply = self.current_supply - quote.token_amount;
+                out_bucket.put(self.xrd_vault.take(quote.xrd_amount.clone()));
+                self.last_price = quote.end_price.clone();
                 Runtime::emit_event(RadixMemeTokenTradeEvent {
                     token_address: self.token_manager.address(),
                     side: String::from("sell"),
-                    fair_launch_period: self.in_fair_launch_period.clone(),
-                    token_amount: tokens_to_sell.clone(),
+                    fair_launch_period: false,
+                    token_amount: quote.token_amount.clone(),
                     xrd_amount: out_bucket.amount(),
+                    fee_perc: quote.fee_perc.clone(),
+                    fee_discount: quote.fee_discount.clone(),
+                    fee_amount: quote.fee_amount.clone(),
                     end_price: self.last_price.clone(),
                 });
             }
             (out_bucket, in_bucket)
         }
 
-        // function to claim tokens allocated during fair launch period
-        pub fn claim_fair_launch_tokens(&mut self, receipts_bucket: Bucket) -> Bucket {
-            let mut out_bucket = Bucket::new(self.token_manager.address());
-            let mut total_xrd = Decimal::ZERO;
-            self.check_in_fair_launch_period();
-            assert!(!self.in_fair_launch_period, "Fair launch period not finished. Fair launch tokens can only be claimed once fair launch period has finished.");
-            assert!(
-                receipts_bucket.resource_address() == self.fair_launch_receipt_manager.address(),
-                "Incorrect tokens submitted for claim."
-            );
-            for receipt in receipts_bucket
-                .as_non_fungible()
-                .non_fungibles::<FairLaunchReceiptData>()
-            {
-                let receipt_data = receipt.data();
-                total_xrd = total_xrd + receipt_data.xrd_amount;
-                let mut claim_tokens = self.fair_launch_tokens.amount() * receipt_data.xrd_amount
-                    / self.fair_launch_xrd;
-                if claim_tokens > self.fair_launch_tokens.amount() {
-                    claim_tokens = self.fair_launch_tokens.amount();
+        // checks whether the fair launch period is still running, without changing the component state
+        fn fair_launch_period_active(&self) -> bool {
+            self.current_status() == CurveStatus::FairLaunch
+        }
+
+        // checks whether the start time of the token has been reached
+        fn trading_started(&self) -> bool {
+            Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch >= self.start_time
+        }
+
+        // checks whether the fair launch period, which is measured from the start time, has passed
+        fn fair_launch_period_ended(&self) -> bool {
+            Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch
+                > self.start_time
+                    + (self
+                        .fair_launch_period_mins
+                        .to_i64()
+                        .expect("Could not convert fair_launch_period_mins to i64")
+                        * 60)
+        }
+
+        // the status a token curve moves to when its start time is reached
+        fn start_status(&self) -> CurveStatus {
+            if self.fair_launch_period_mins > 0 {
+                CurveStatus::FairLaunch
+            } else {
+                CurveStatus::Trading
+            }
+        }
+
+        // splits the tx fee between the creator_fee_vault (the creator_fee_share) and the fee_vault (the rest)
+        // if a referrer is specified, the referrer's share of the rest is paid to the referrer's balance on the parent component
+        fn deposit_fees(&mut self, mut fee_bucket: Bucket, referrer: Option<NonFungibleLocalId>) {
+            let creator_fee = fee_bucket.amount() * self.creator_fee_share;
+            if creator_fee > Decimal::ZERO {
+                self.creator_fee_vault.put(fee_bucket.take(creator_fee));
+            }
+            if let Some(referrer_id) = referrer {
+                let parent_instance = Global::<RadixMemeMain>::from(self.parent_address.clone());
+                fee_bucket = parent_instance.deposit_referral_fee(
+                    self.address.clone(),
+                    referrer_id,
+                    fee_bucket,
+                );
+            }
+            self.fee_vault.put(fee_bucket);
+            self.forward_fees();
+        }
+
+        // sends the fees in the fee_vault to the parent component once the fee_forward_threshold has been reached
+        fn forward_fees(&mut self) {
+            if let Some(threshold) = self.fee_forward_threshold {
+                let fee_amount = self.fee_vault.amount();
+                if fee_amount > Decimal::ZERO && fee_amount >= threshold {
+                    let parent_instance =
+                        Global::<RadixMemeMain>::from(self.parent_address.clone());
+                    parent_instance.transfer_fees(self.fee_vault.take_all());
+                    Runtime::emit_event(RadixMemeForwardFeeEvent {
+                        token_address: self.token_manager.address(),
+                        fee_forwarded: fee_amount,
+                    });
                 }
-                out_bucket.put(self.fair_launch_tokens.take(claim_tokens));
-                self.fair_launch_xrd = self.fair_launch_xrd - receipt_data.xrd_amount;
             }
-            receipts_bucket.burn();
-            Runtime::emit_event(RadixMemeClaimTokensEvent {
-                tokens_claimed: out_bucket.amount(),
-                xrd_amount: total_xrd.clone(),
-            });
-            out_bucket
         }
 
-        pub fn claim_all_fees(&mut self) -> Bucket {
-            Runtime::emit_event(RadixMemeClaimFeeEvent {
-                fee_claimed: self.fee_vault.amount(),
+        // rejects trades submitted with a deadline (seconds since unix epoch) that has already passed
+        fn check_deadline(deadline: Option<i64>) {
+            if let Some(deadline) = deadline {
+                let now = Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
+                assert!(
+                    now <= deadline,
+                    "Trade deadline expired. Deadline: {}, current time: {}",
+                    deadline,
+                    now
+                );
+            }
+        }
+
+        // the status of the token curve at this point in time. The stored status is only updated by the next tx,
+        // so a scheduled token whose start time has passed is reported as in its fair launch (or Trading without a fair launch)
+        // and a fair launch that has expired is reported as Trading, or as Refunding if it raised less than the fair launch min XRD
+        fn current_status(&self) -> CurveStatus {
+            let mut status = self.status;
+            if status == CurveStatus::Scheduled && self.trading_started() {
+                status = self.start_status();
+            }
+            if status == CurveStatus::FairLaunch && self.fair_launch_period_ended() {
+                status = if self.fair_launch_xrd < self.fair_launch_min_xrd {
+                    CurveStatus::Refunding
+                } else {
+                    CurveStatus::Trading
+                };
+            }
+            status
+        }
+
+        // updates the stored status to the current status, e.g. when the start time is reached or the fair launch period has ended
+        fn update_status(&mut self) {
+            if self.status == CurveStatus::Scheduled && self.trading_started() {
+                self.set_status(self.start_status());
+            }
+            if self.status == CurveStatus::FairLaunch && self.fair_launch_period_ended() {
+                self.end_fair_launch();
+            }
+        }
+
+        // changes the status of the token curve and updates the status kept by the parent component
+        fn set_status(&mut self, new_status: CurveStatus) {
+            Runtime::emit_event(RadixMemeStatusChangeEvent {
+                token_address: self.token_manager.address(),
+                old_status: self.status,
+                new_status,
             });
-            self.fee_vault.take_all()
+            self.status = new_status;
+            let parent_instance = Global::<RadixMemeMain>::from(self.parent_address.clone());
+            parent_instance.update_token_status(self.address.clone(), new_status);
         }
 
-        fn check_in_fair_launch_period(&mut self) {
-            if self.in_fair_launch_period
-                && Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch
-                    > self.time_created
-                        + (self
-                            .fair_launch_period_mins
-                            .to_i64()
-                            .expect("Could not convert fair_launch_period_mins to i64")
-                            * 60)
-            {
-                self.end_fair_launch_period();
+        // rejects trades on the bonding curve once the token has graduated
+        fn check_not_graduated(&self) {
+            if self.status == CurveStatus::Graduated {
+                match self.pool_address {
+                    Some(pool_address) => panic!(
+                        "Token has graduated and can no longer be traded on the bonding curve. Trade the token on pool {:?}.",
+                        pool_address
+                    ),
+                    None => panic!(
+                        "Token has graduated and can no longer be traded on the bonding curve."
+                    ),
+                }
             }
         }
 
-        fn end_fair_launch_period(&mut self) {
-            self.in_fair_launch_period = false;
+        // the pool can only swap an exact input amount, so trades for an exact amount of tokens or XRD cannot be routed through it
+        fn check_exact_amount_not_routed(&self) {
+            assert!(
+                self.status != CurveStatus::Graduated || !self.route_graduated_trades,
+                "Exact amount trades cannot be routed through the pool after the token has graduated. Use buy or sell instead."
+            );
         }
 
-        // method to launch the token on DEX(s)
-        fn list_token(&mut self) {
-            info!("Token will be listed!");
+        // returns the built-in pool the token was listed on if the token has graduated and its trades are routed through the pool
+        // returns None if trades are not routed, in which case the trade methods keep rejecting trades after graduation
+        fn routed_pool(&self) -> Option<Global<RadixMemePool>> {
+            if self.status != CurveStatus::Graduated || !self.route_graduated_trades {
+                return None;
+            }
+            match (self.dex_adapter, self.pool_address) {
+                (None, Some(pool_address)) => Some(Global::<RadixMemePool>::from(pool_address)),
+                _ => panic!("Token has graduated, but was not listed on a pool that trades can be routed through."),
+            }
         }
 
-        // the following calculation functions are all pure functions that (in future) can be moved to seperate components that represent different bonding curves.
-        // This will allow for easier upgradability as well as easier addition of different types of bonding curves.
+        // swaps the bucket on the built-in pool the token was listed on, for buys and sells on a graduated token curve
+        // the swap only pays the pool fee and must return at least min_out (if specified). No tx fee is charged on routed trades,
+        // so pass discounts and referrers do not apply to them
+        // returns a bucket with the swap result as well as an empty bucket of the resource that was sent, to match the trade methods
+        fn route_to_pool(
+            pool: Global<RadixMemePool>,
+            in_bucket: Bucket,
+            min_out: Option<Decimal>,
+        ) -> (Bucket, Bucket) {
+            let in_address = in_bucket.resource_address();
+            let out_bucket = pool.swap(in_bucket);
+            if let Some(min_amount) = min_out {
+                assert!(
+                    out_bucket.amount() >= min_amount,
+                    "Slippage limit exceeded. Amount received ({}) less than minimum ({}).",
+                    out_bucket.amount(),
+                    min_amount
+                );
+            }
+            (out_bucket, Bucket::new(in_address))
+        }
 
-        // pure function to calculate the current price on the bonding curve based on the current token supply
-        fn calculate_price(supply: &Decimal, multiplier: &PreciseDecimal) -> Decimal {
-            Decimal::try_from(
-                multiplier.clone()
-                    * PreciseDecimal::from(supply.clone())
-                        .checked_powi(2)
-                        .expect("calculate_price problem. powi(2)"),
-            )
-            .expect("calculate_price problem. Cant convert precise decimal to decimal.")
-        }
-
-        // pure function to calculate the buy price (XRD required) in order to receive a specified amount of new tokens
-        fn calculate_buy_price(
-            new_tokens: Decimal,        // the amount of tokens to buy
-            supply: Decimal,            // the supply of tokens before the buy transaction
-            multiplier: PreciseDecimal, // the constant multiplier to use in the calcs (mased on max supply and max xrd)
-        ) -> Decimal {
-            let mut result = Decimal::ZERO;
-            if new_tokens > Decimal::ZERO {
-                let precise_supply = PreciseDecimal::from(supply.clone());
-                let first_value: PreciseDecimal = multiplier
-                    .checked_div(3)
-                    .expect("calculate_buy_price problem. Div 3");
-                let second_value = (precise_supply + new_tokens.clone())
-                    .checked_powi(3)
-                    .expect("calculate_buy_price problem. First Powi(3).");
-                let third_value = precise_supply
-                    .checked_powi(3)
-                    .expect("calculate_buy_price problem. Second Powi(3).");
-                let fourth_value = second_value + third_value;
-                let precise_price = first_value
-                    .checked_mul(fourth_value)
-                    .expect("calculate_buy_price problem. Final Multiply.");
-                result = Decimal::try_from(
-                    precise_price
-                        .checked_round(18, RoundingMode::ToNearestMidpointAwayFromZero)
-                        .expect("calculate_buy_price problem. Cant round precise decimal."),
+        // quotes a swap on the built-in pool, as it would be executed by route_to_pool
+        // the pool fee is charged on the input, so fee_amount is in XRD for buys and in tokens for sells.
+        // end_price is the pool price after the swap and target_reached is always false, as the token has already graduated
+        fn quote_routed_swap(
+            pool: Global<RadixMemePool>,
+            input_address: ResourceAddress,
+            input_amount: Decimal,
+        ) -> TradeQuote {
+            let (output_amount, fee_amount) = pool.quote_swap(input_address, input_amount);
+            let (xrd_reserve, token_reserve) = pool.get_reserves();
+            let (token_amount, xrd_amount, end_price) = if input_address == XRD {
+                (
+                    output_amount,
+                    input_amount - fee_amount,
+                    (xrd_reserve + input_amount) / (token_reserve - output_amount),
                 )
-                .expect("calculate_buy_price problem. Cant convert precise decimal to decimal.")
-            }
-            result
-        }
-
-        // pure function to calculate how many tokens can be bought with the specified amount of XRD
-        fn calculate_tokens_received(
-            xrd_received: Decimal,      // the amount of XRD to spend to buy tokens
-            supply: Decimal,            // the supply of tokens before the buy transaction
-            multiplier: PreciseDecimal, // the constant multiplier to use in the calcs (mased on max supply and max xrd)
-        ) -> Decimal {
-            let mut result = Decimal::ZERO;
-            if xrd_received > Decimal::ZERO {
-                info!("Miltiplier: {}", multiplier);
-                let precise_xrd_received = PreciseDecimal::from(xrd_received.clone());
-                info!("XRD Received: {}", precise_xrd_received);
-                let precise_supply = PreciseDecimal::from(supply.clone());
-                info!("Supply: {}", precise_supply);
-                let mut first_value = precise_xrd_received
-                    .checked_div(multiplier.clone())
-                    .expect("calculate_tokens_received problem. First div");
-                first_value = first_value
-                    .checked_mul(3)
-                    .expect("calculate_tokens_received problem. First mul");
-                info!("First value: {}", first_value);
-                let second_value = precise_supply
-                    .checked_powi(3)
-                    .expect("calculate_tokens_received problem. First powi");
-                info!("Second value: {}", second_value);
-                let third_value = (first_value + second_value)
-                    .checked_nth_root(3)
-                    .expect("calculate_tokens_received problem. First root");
-                info!("Third value: {}", third_value);
-                let precise_result = third_value - precise_supply;
-                info!("Result: {}", precise_result);
-                result = Decimal::try_from(
-                    precise_result
-                        .checked_round(18, RoundingMode::ToNearestMidpointAwayFromZero)
-                        .expect("calculate_tokens_received problem. Cant round precise decimal."),
+            } else {
+                (
+                    input_amount,
+                    output_amount,
+                    (xrd_reserve - output_amount) / (token_reserve + input_amount),
                 )
-                .expect(
-                    "calculate_tokens_received problem. Cant convert precise decimal to decimal.",
-                );
+            };
+            TradeQuote {
+                token_amount,
+                xrd_amount,
+                fee_amount,
+                fee_perc: fee_amount / input_amount,
+                fee_discount: Decimal::ZERO,
+                end_price,
+                target_reached: false,
             }
-            result
         }
 
-        // function to calculate the sell price (XRD received) from selling the speficied number of tokens
-        fn calculate_sell_price(
-            sell_tokens: Decimal,       // the amount of tokens to sell
-            supply: Decimal,            // the supply of tokens before the buy transaction
-            multiplier: PreciseDecimal, // the constant multiplier to use in the calcs (mased on max supply and max xrd)
-        ) -> Decimal {
-            let mut result = Decimal::ZERO;
-            if sell_tokens > Decimal::ZERO {
-                let precise_supply = PreciseDecimal::from(supply.clone());
-                let precise_new_supply = precise_supply.clone() - sell_tokens.clone();
+        // rejects an action that is not allowed with the specified status
+        fn check_status(status: CurveStatus, allowed_statuses: &[CurveStatus], action: &str) {
+            assert!(
+                allowed_statuses.contains(&status),
+                "Cannot {} when the token status is {:?}.",
+                action,
+                status
+            );
+        }
 
-                let first_value: PreciseDecimal = multiplier
-                    .clone()
-                    .checked_div(3)
-                    .expect("calculate_buy_price problem. Div 3");
-                let second_value = (precise_supply.clone())
-                    .checked_powi(3)
-                    .expect("calculate_buy_price problem. First Powi(3).");
-                let third_value = (precise_new_supply.clone())
-                    .checked_powi(3)
-                    .expect("calculate_buy_price problem. Second Powi(3).");
-                let fourth_value = second_value - third_value;
-
-                let precise_price = first_value
-                    .checked_mul(fourth_value)
-                    .expect("calculate_sell_price problem. Multiplication problem.");
-                result = Decimal::try_from(
-                    precise_price
-                        .checked_round(18, RoundingMode::ToNearestMidpointAwayFromZero)
-                        .expect("calculate_sell_price problem. Cant round precise decimal."),
-                )
-                .expect("calculate_sell_price problem. Cant convert precise decimal to decimal.");
-            }
-            // info!("Sell price: {:?}", result);
-            result
-        }
-
-        // function to calculate the amount of tokens to sell to receiv the specified amount of XRD
-        fn calculate_tokens_to_sell(
-            xrd_required: Decimal,      // the amount of XRD to receive from selling tokens
-            supply: Decimal,            // the supply of tokens before the buy transaction
-            multiplier: PreciseDecimal, // the constant multiplier to use in the calcs (mased on max supply and max xrd)
-        ) -> Decimal {
-            let mut result = Decimal::ZERO;
-            if xrd_required > Decimal::ZERO {
-                let precise_xrd_required = PreciseDecimal::from(xrd_required.clone());
-                info!("Precise XRD required: {:?}", precise_xrd_required);
-                let precise_supply = PreciseDecimal::from(supply.clone());
-                info!("Precise supply: {:?}", precise_supply);
-                let mut first_value: PreciseDecimal = precise_xrd_required
-                    .checked_mul(3)
-                    .expect("calculate_tokens_to_sell problem. First mul");
-                info!("First value: {}", first_value);
-                first_value = first_value
-                    .checked_div(multiplier.clone())
-                    .expect("calculate_tokens_to_sell problem. First div");
-                info!("First value: {}", first_value);
-                let second_value = precise_supply
-                    .checked_powi(3)
-                    .expect("calculate_tokens_to_sell problem. First powi");
-                info!("Second value: {:?}", second_value);
-                let third_value = second_value - first_value;
-                info!("Third value: {:?}", third_value);
-                let fourth_value = third_value
-                    .checked_nth_root(3)
-                    .expect("calculate_tokens_to_sell problem. First root");
-                info!("Fourth value: {:?}", fourth_value);
-                let precise_result = precise_supply - fourth_value;
-                info!("Precise Result: {:?}", precise_result);
-                result = Decimal::try_from(
-                    precise_result
-                        .checked_round(18, RoundingMode::ToNearestMidpointAwayFromZero)
-                        .expect("calculate_tokens_to_sell problem. Cant round precise decimal."),
-                )
-                .expect(
-                    "calculate_tokens_to_sell problem. Cant convert precise decimal to decimal.",
+        // method that deposits the LP tokens received at graduation into a new RadixMemeLpLock component, where they are either
+        // locked forever or until the end of the lock period, depending on the lp_token_policy of the token.
+        // The token curve keeps no LP tokens and has no method to withdraw them.
+        fn lock_lp_tokens(&mut self, lp_bucket: Bucket) {
+            if let LpTokenPolicy::TimeLock { lock_period_mins } = self.lp_token_policy {
+                self.lp_unlock_time = Some(
+                    Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch
+                        + i64::from(lock_period_mins) * 60,
                 );
             }
-            result
+            let lp_resource = lp_bucket.resource_address();
+            let lp_amount = lp_bucket.amount();
+            let lp_lock = Blueprint::<RadixMemeLpLock>::new(
+                lp_bucket,
+                self.lp_unlock_time.clone(),
+                self.radix_meme_admin_rule.clone(),
+            );
+            self.lp_lock_address = Some(lp_lock.address());
+            self.lp_resource = Some(lp_resource.clone());
+            Runtime::emit_event(RadixMemeLpTokensEvent {
+                token_address: self.token_manager.address(),
+                pool_address: self
+                    .pool_address
+                    .clone()
+                    .expect("LP tokens received without a pool."),
+                lp_lock_address: lp_lock.address(),
+                lp_resource,
+                lp_amount,
+                lp_token_policy: self.lp_token_policy.clone(),
+                unlock_time: self.lp_unlock_time.clone(),
+            });
         }
 
-        fn calculate_multiplier(
-            max_xrd_market_cap: Decimal,
-            max_token_supply_to_trade: Decimal,
-        ) -> PreciseDecimal {
-            let divisor = PreciseDecimal::from(max_token_supply_to_trade)
-                .checked_powi(3)
-                .expect("Problem in calculating multiplier. powi(3)");
-            let multiplier = PreciseDecimal::from(max_xrd_market_cap)
-                .checked_div(divisor)
-                .expect("Problem in calculating multiplier. First div");
-            multiplier
-        }
-
-        fn calculate_max_xrd(
-            multiplier: PreciseDecimal,
-            max_token_supply_to_trade: Decimal,
-        ) -> Decimal {
-            let first_value: PreciseDecimal = multiplier
-                .checked_div(3)
-                .expect("Problem in calculating max_xrd. First div");
-            let precise_max_supply = PreciseDecimal::from(max_token_supply_to_trade);
-            let second_value: PreciseDecimal = precise_max_supply
-                .checked_powi(3)
-                .expect("Problem in calculating max_xrd. First powi");
-            let precise_max_xrd: PreciseDecimal = first_value
-                .checked_mul(second_value)
-                .expect("Problem calculating max_xrd. First mul");
-            Decimal::try_from(precise_max_xrd)
-                .expect("Problem calculating max_xrd. Could not convert precise_max_xrd to decimal")
+        // method to launch the token on DEX(s) once the target market cap has been reached
+        // the listing fee is paid to the parent component and the creator fee to the creator_fee_vault before the token is marked as graduated
+        // the remaining XRD is added as liquidity at the final curve price, together with newly minted tokens from the supply that was not traded on the curve,
+        // either through the DEX adapter of the token or to a new RadixMemePool if the token has no DEX adapter.
+        // XRD that the DEX adapter does not add as liquidity is sent to the parent component, so the xrd_vault is empty once the token has graduated
+        fn list_token(&mut self) {
+            assert!(
+                self.status == CurveStatus::Graduating,
+                "Token can only be listed when it is graduating."
+            );
+            let xrd_raised = self.xrd_vault.amount();
+            let listing_fee = xrd_raised * self.listing_fee_perc;
+            let creator_fee = xrd_raised * self.creator_fee_perc;
+            if listing_fee > Decimal::ZERO {
+                let parent_instance = Global::<RadixMemeMain>::from(self.parent_address.clone());
+                parent_instance.transfer_fees(self.xrd_vault.take(listing_fee));
+            }
+            if creator_fee > Decimal::ZERO {
+                self.creator_fee_vault.put(self.xrd_vault.take(creator_fee));
+            }
+            let xrd_remaining = self.xrd_vault.amount();
+            // the pool is seeded at the final price on the curve, so only the tokens that match the remaining XRD at that price
+            // are minted. The rest of the supply that was not traded on the curve is never minted.
+            let pool_token_amount = if self.last_price > Decimal::ZERO {
+                (xrd_remaining / self.last_price)
+                    .min(self.max_token_supply - self.max_token_supply_to_trade)
+            } else {
+                Decimal::ZERO
+            };
+            let mut unused_xrd = Decimal::ZERO;
+            if pool_token_amount > Decimal::ZERO && xrd_remaining > Decimal::ZERO {
+                let xrd_bucket = self.xrd_vault.take_all();
+                let token_bucket = self.token_manager.mint(pool_token_amount);
+                let (pool_address, lp_bucket) = match self.dex_adapter.clone() {
+                    Some(adapter_address) => {
+                        let (pool_address, lp_bucket, unused_buckets) =
+                            dex_adapter::list_on_dex(adapter_address, xrd_bucket, token_bucket);
+                        // any XRD not used by the DEX is sent to the parent component and unused tokens are burned
+                        for unused_bucket in unused_buckets {
+                            if unused_bucket.resource_address() == XRD {
+                                unused_xrd += unused_bucket.amount();
+                                let parent_instance =
+                                    Global::<RadixMemeMain>::from(self.parent_address.clone());
+                                parent_instance.transfer_fees(unused_bucket);
+                            } else {
+                                assert!(
+                                    unused_bucket.resource_address()
+                                        == self.token_manager.address(),
+                                    "DEX adapter returned an unexpected resource: {:?}",
+                                    unused_bucket.resource_address()
+                                );
+                                self.token_manager.burn(unused_bucket);
+                            }
+                        }
+                        (pool_address, lp_bucket)
+                    }
+                    None => {
+                        let (pool, lp_bucket) = Blueprint::<RadixMemePool>::new(
+                            xrd_bucket,
+                            token_bucket,
+                            self.pool_fee_perc.clone(),
+                            self.radix_meme_admin_rule.clone(),
+                        );
+                        (pool.address(), lp_bucket)
+                    }
+                };
+                self.pool_address = Some(pool_address);
+                self.lock_lp_tokens(lp_bucket);
+            }
+            Runtime::emit_event(RadixMemeTokenGraduatedEvent {
+                token_address: self.token_manager.address(),
+                component_address: self.address.clone(),
+                xrd_raised,
+                listing_fee,
+                creator_fee,
+                xrd_remaining,
+                xrd_to_parent: unused_xrd,
+                dex_adapter: self.dex_adapter.clone(),
+                pool_address: self.pool_address.clone(),
+                pool_token_amount: if self.pool_address.is_some() {
+                    pool_token_amount
+                } else {
+                    Decimal::ZERO
+                },
+                final_supply: self.current_supply.clone(),
+                final_price: self.last_price.clone(),
+                time_graduated: self.target_reached.clone(),
+            });
+            self.set_status(CurveStatus::Graduated);
         }
     }
 }
@@ -1,3 +1,7 @@
+use meme_token::bonding_curve::{BondingCurve, BondingCurveConfig};
+use meme_token::curve_math::{self, CurveState, FeeSchedule};
+use meme_token::dex_adapter::LpTokenPolicy;
+use meme_token::radix_meme_token_curve::CurveStatus;
 use scrypto_test::prelude::*;
 
 pub mod utils;
@@ -241,6 +245,58 @@ fn simple_buy_sell_tests() {
     );
 }
 
+#[test]
+fn buy_amount_from_non_zero_supply_tests() {
+    let mut env = utils::setup_test_env(0, false);
+    utils::txs::token_buy_amount(
+        dec!("100000"),
+        dec!("1000"),
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    // the default curve is price = supply^2 / 10^12, so buying from 0 to 100000 costs 100000^3 / (3 * 10^12)
+    let component_xrd_before = env
+        .test_runner
+        .get_component_balance(env.token1_component, XRD);
+    assert!(
+        component_xrd_before == dec!("333.333333333333333333"),
+        "Incorrect XRD in component after first buy amount. {:?}",
+        component_xrd_before
+    );
+
+    // buying from 100000 to 150000 costs (150000^3 - 100000^3) / (3 * 10^12)
+    utils::txs::token_buy_amount(
+        dec!("50000"),
+        dec!("1000"),
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&env.token1_component, &mut env.test_runner);
+    assert!(
+        token_state.current_supply == dec!("150000"),
+        "Incorrect supply after second buy amount. {:?}",
+        token_state.current_supply
+    );
+    let component_xrd_after = env
+        .test_runner
+        .get_component_balance(env.token1_component, XRD);
+    assert!(
+        component_xrd_after - component_xrd_before == dec!("791.666666666666666667"),
+        "Incorrect XRD paid for buy amount from a non-zero supply. {:?}",
+        component_xrd_after - component_xrd_before
+    );
+    let xrd_balance = env
+        .test_runner
+        .get_component_balance(env.owner_account.address.clone(), XRD);
+    assert!(
+        xrd_balance == dec!("10000") - component_xrd_after,
+        "Unused XRD should be returned after buy amount. {:?}",
+        xrd_balance
+    );
+}
+
 #[test]
 fn buy_sell_all_tokens_tests() {
     let mut env = utils::setup_test_env(0, false);
@@ -267,13 +323,21 @@ fn buy_sell_all_tokens_tests() {
         "Incorrect supply after buy. {:?}",
         token_state.current_supply
     );
-    let component_xrd_balance = env
+    // all XRD raised on the curve is moved to the pool the token is listed on when it graduates
+    let pool_state = utils::pool::get_pool_state(
+        &token_state
+            .pool_address
+            .expect("Pool should be created when the token graduates."),
+        &mut env.test_runner,
+    );
+    let pool_xrd_balance = env
         .test_runner
-        .get_component_balance(env.token1_component, XRD);
+        .inspect_vault_balance(pool_state.xrd_vault.0 .0)
+        .unwrap();
     assert!(
-        component_xrd_balance == dec!("333333.333333333333333333"),
-        "Incorrect XRD in component after first buy. {:?}",
-        component_xrd_balance
+        pool_xrd_balance == dec!("333333.333333333333333333"),
+        "Incorrect XRD in pool after first buy. {:?}",
+        pool_xrd_balance
     );
     let token_balance = env.test_runner.get_component_balance(
         env.owner_account.address.clone(),
@@ -293,51 +357,2738 @@ fn buy_sell_all_tokens_tests() {
         xrd_balance.clone()
     );
 
-    let _first_sell_receipt = utils::txs::token_sell_for_xrd_amount(
+    let token_state = utils::token::get_token_state(&env.token1_component, &mut env.test_runner);
+    assert!(
+        token_state.status == CurveStatus::Graduated,
+        "Token should be graduated after buying all tokens. {:?}",
+        token_state.status
+    );
+
+    // once the token has graduated, it can no longer be traded on the bonding curve
+    let sell_receipt = utils::txs::token_sell_for_xrd_amount_with_limit(
         dec!("333333.333333333333333333"),
         dec!(1000000),
+        None,
+        None,
         &env.token1_address,
         &env.owner_account,
         &env.token1_component,
         &mut env.test_runner,
     );
-    println!("Sell receipt: {:?}", _first_sell_receipt);
-    utils::token::show_token_state(&env.token1_component, &mut env.test_runner);
-    let token_state = utils::token::get_token_state(&env.token1_component, &mut env.test_runner);
     assert!(
-        token_state.last_price == dec!("0.0000000000000001"), // result not zero because of limited accuracy of scrypto calcs
-        "Incorrect price after sell. {:?}",
+        sell_receipt.is_commit_failure(),
+        "Sells should be rejected after the token has graduated."
+    );
+    let buy_receipt = utils::txs::token_buy_with_limit(
+        dec!(100),
+        None,
+        None,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        buy_receipt.is_commit_failure(),
+        "Buys should be rejected after the token has graduated."
+    );
+}
+
+#[test]
+fn linear_curve_buy_sell_tests() {
+    let mut env = utils::setup_test_env(0, false);
+    let (token_component, _token_dapp_def, token_address) =
+        utils::token::create_token_curve_component_advanced(
+            String::from("Linear Token"),
+            String::from("LINEAR"),
+            String::from("A token on a linear bonding curve"),
+            String::from("https://dexteronradix.com/dexter-logo-and-lettering.svg"),
+            String::from(""),
+            String::from(""),
+            String::from("https://radix.meme"),
+            BondingCurveConfig::Linear {
+                initial_price: dec!("0.5"),
+            },
+            None,
+            env.token_creation_fee.clone(),
+            &env.parent_component_address,
+            &env.owner_account,
+            &mut env.test_runner,
+        );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    assert!(
+        token_state.max_xrd == dec!("750000"),
+        "Incorrect max xrd for linear curve. {:?}",
+        token_state.max_xrd
+    );
+
+    let _buy_receipt = utils::txs::token_buy(
+        dec!(100),
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    utils::token::show_token_state(&token_component, &mut env.test_runner);
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    let expected_price = dec!("0.5") + dec!("0.0000005") * token_state.current_supply;
+    assert!(
+        token_state.last_price - expected_price < dec!("0.000000000001")
+            && expected_price - token_state.last_price < dec!("0.000000000001"),
+        "Incorrect price after buy on linear curve. {:?}",
         token_state.last_price
     );
     assert!(
-        token_state.current_supply == dec!("0.01"),
-        "Incorrect supply after sell. {:?}",
+        token_state.current_supply > dec!("199") && token_state.current_supply < dec!("200"),
+        "Incorrect supply after buy on linear curve. {:?}",
         token_state.current_supply
     );
-    let component_xrd_balance = env
+    let token_balance = env
         .test_runner
-        .get_component_balance(env.token1_component, XRD);
+        .get_component_balance(env.owner_account.address.clone(), token_address.clone());
     assert!(
-        component_xrd_balance == dec!("0"),
-        "Incorrect XRD in component after first sell. {:?}",
+        token_balance == token_state.current_supply,
+        "Incorrect token balance in account after buy on linear curve. {:?}",
+        token_balance
+    );
+
+    let _sell_receipt = utils::txs::token_sell(
+        token_balance,
+        &token_address,
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    assert!(
+        token_state.current_supply == dec!("0"),
+        "Incorrect supply after sell on linear curve. {:?}",
+        token_state.current_supply
+    );
+    assert!(
+        token_state.last_price == dec!("0.5"),
+        "Incorrect price after sell on linear curve. {:?}",
+        token_state.last_price
+    );
+    let component_xrd_balance = env.test_runner.get_component_balance(token_component, XRD);
+    assert!(
+        component_xrd_balance < dec!("0.000000000001"),
+        "Incorrect XRD in component after sell on linear curve. {:?}",
         component_xrd_balance
     );
-    let token_balance = env.test_runner.get_component_balance(
-        env.owner_account.address.clone(),
-        env.token1_address.clone(),
+}
+
+#[test]
+fn power_curve_exponent_tests() {
+    let mut env = utils::setup_test_env(0, false);
+    utils::load_account_with_xrd(&env.owner_account, dec!("500000"), &mut env.test_runner);
+    let (token_component, _token_dapp_def, token_address) =
+        utils::token::create_token_curve_component_advanced(
+            String::from("Power Token"),
+            String::from("POWER"),
+            String::from("A token on a power law bonding curve"),
+            String::from("https://dexteronradix.com/dexter-logo-and-lettering.svg"),
+            String::from(""),
+            String::from(""),
+            String::from("https://radix.meme"),
+            BondingCurveConfig::Power {
+                exponent: dec!("1.5"),
+                initial_price: dec!("0"),
+            },
+            None,
+            env.token_creation_fee.clone(),
+            &env.parent_component_address,
+            &env.owner_account,
+            &mut env.test_runner,
+        );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    // max xrd is max_xrd_market_cap / (exponent + 1)
+    assert!(
+        token_state.max_xrd - dec!("400000") < dec!("0.000001")
+            && dec!("400000") - token_state.max_xrd < dec!("0.000001"),
+        "Incorrect max xrd for power curve. {:?}",
+        token_state.max_xrd
+    );
+
+    let _buy_receipt = utils::txs::token_buy(
+        dec!(500000),
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
     );
+    utils::token::show_token_state(&token_component, &mut env.test_runner);
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
     assert!(
-        token_balance == dec!("0.01"),
-        "Incorrect token Balance in account after first sell. {:?}",
-        token_balance.clone()
+        dec!("1000000") - token_state.current_supply < dec!("0.000001"),
+        "Incorrect supply after buying all tokens on power curve. {:?}",
+        token_state.current_supply
     );
-    let xrd_balance = env
+    assert!(
+        dec!("1") - token_state.last_price < dec!("0.000001"),
+        "Incorrect price after buying all tokens on power curve. {:?}",
+        token_state.last_price
+    );
+    let token_balance = env
+        .test_runner
+        .get_component_balance(env.owner_account.address.clone(), token_address);
+    assert!(
+        token_balance == token_state.current_supply,
+        "Incorrect token balance in account after buy on power curve. {:?}",
+        token_balance
+    );
+}
+
+#[test]
+fn power_curve_large_supply_tests() {
+    let mut env = utils::setup_test_env(0, false);
+    utils::load_account_with_xrd(&env.owner_account, dec!("100000"), &mut env.test_runner);
+    utils::parent::change_default_parameter(
+        "max_token_supply",
+        "2000000000",
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    utils::parent::change_default_parameter(
+        "max_token_supply_to_trade",
+        "1000000000",
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    let (token_component, _token_dapp_def, token_address) =
+        utils::token::create_token_curve_component_advanced(
+            String::from("Large Token"),
+            String::from("LARGE"),
+            String::from("A token with a large supply on a steep power curve"),
+            String::from("https://dexteronradix.com/dexter-logo-and-lettering.svg"),
+            String::from(""),
+            String::from(""),
+            String::from("https://radix.meme"),
+            BondingCurveConfig::Power {
+                exponent: dec!("5"),
+                initial_price: dec!("0"),
+            },
+            None,
+            env.token_creation_fee.clone(),
+            &env.parent_component_address,
+            &env.owner_account,
+            &mut env.test_runner,
+        );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    // max xrd is max_xrd_market_cap / (exponent + 1)
+    assert!(
+        token_state.max_xrd - dec!("166666.666666666666666666") < dec!("0.000001")
+            && dec!("166666.666666666666666666") - token_state.max_xrd < dec!("0.000001"),
+        "Incorrect max xrd for power curve with a large supply. {:?}",
+        token_state.max_xrd
+    );
+
+    // buying 100000 XRD of tokens moves the supply to max_supply * 0.6^(1/6)
+    let _buy_receipt = utils::txs::token_buy(
+        dec!(100000),
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    assert!(
+        token_state.current_supply - dec!("918385902.168445333352545257") < dec!("0.000001")
+            && dec!("918385902.168445333352545257") - token_state.current_supply < dec!("0.000001"),
+        "Incorrect supply after buy on power curve with a large supply. {:?}",
+        token_state.current_supply
+    );
+    assert!(
+        token_state.last_price - dec!("0.000653320133272201") < dec!("0.000000000001")
+            && dec!("0.000653320133272201") - token_state.last_price < dec!("0.000000000001"),
+        "Incorrect price after buy on power curve with a large supply. {:?}",
+        token_state.last_price
+    );
+
+    let _sell_receipt = utils::txs::token_sell(
+        token_state.current_supply,
+        &token_address,
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    assert!(
+        token_state.current_supply == Decimal::ZERO,
+        "Incorrect supply after selling all tokens on power curve with a large supply. {:?}",
+        token_state.current_supply
+    );
+    let component_xrd_balance = env.test_runner.get_component_balance(token_component, XRD);
+    assert!(
+        component_xrd_balance < dec!("0.000001"),
+        "Incorrect XRD in component after selling all tokens on power curve with a large supply. {:?}",
+        component_xrd_balance
+    );
+}
+
+#[test]
+fn virtual_reserve_curve_default_tests() {
+    let mut env = utils::setup_test_env(0, false);
+    let _change_receipt = utils::parent::change_default_bonding_curve(
+        BondingCurveConfig::VirtualReserve {
+            virtual_token_reserve: dec!("2000000"),
+        },
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    let (token_component, _token_dapp_def, token_address) =
+        utils::token::create_token_curve_component(
+            String::from("Reserve Token"),
+            String::from("RESERVE"),
+            String::from("A token on a virtual reserve bonding curve"),
+            String::from("https://dexteronradix.com/dexter-logo-and-lettering.svg"),
+            String::from(""),
+            String::from(""),
+            String::from("https://radix.meme"),
+            env.token_creation_fee.clone(),
+            &env.parent_component_address,
+            &env.owner_account,
+            &mut env.test_runner,
+        );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    // the virtual xrd reserve is solved from the max market cap: 1000000 * (2000000 - 1000000)^2 / (1000000 * 2000000) = 500000,
+    // so max xrd is constant_product / (virtual_token_reserve - max_supply) - virtual_xrd_reserve = 1000000 - 500000
+    assert!(
+        token_state.max_xrd == dec!("500000"),
+        "Incorrect max xrd for virtual reserve curve. {:?}",
+        token_state.max_xrd
+    );
+    // the price at the max supply matches the max market cap, as on the other curves
+    assert!(
+        token_state.bonding_curve.calculate_price(dec!("1000000")) == dec!("1"),
+        "Incorrect price at the max supply on virtual reserve curve."
+    );
+
+    utils::load_account_with_xrd(&env.owner_account, dec!("125000"), &mut env.test_runner);
+    let _buy_receipt = utils::txs::token_buy(
+        dec!(125000),
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    utils::token::show_token_state(&token_component, &mut env.test_runner);
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    // xrd reserve goes from 500000 to 625000, so the token reserve goes from 2000000 to 1600000
+    assert!(
+        token_state.current_supply == dec!("400000"),
+        "Incorrect supply after buy on virtual reserve curve. {:?}",
+        token_state.current_supply
+    );
+    assert!(
+        token_state.last_price == dec!("0.390625"),
+        "Incorrect price after buy on virtual reserve curve. {:?}",
+        token_state.last_price
+    );
+
+    let _sell_receipt = utils::txs::token_sell(
+        dec!("200000"),
+        &token_address,
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    // token reserve goes to 1800000, so the xrd reserve goes to 1000000000000 / 1800000
+    assert!(
+        token_state.current_supply == dec!("200000"),
+        "Incorrect supply after sell on virtual reserve curve. {:?}",
+        token_state.current_supply
+    );
+    let component_xrd_balance = env.test_runner.get_component_balance(token_component, XRD);
+    assert!(
+        component_xrd_balance == dec!("55555.555555555555555556"),
+        "Incorrect XRD in component after sell on virtual reserve curve. {:?}",
+        component_xrd_balance
+    );
+}
+
+#[test]
+fn power_curve_initial_price_tests() {
+    let mut env = utils::setup_test_env(0, false);
+    utils::load_account_with_xrd(&env.owner_account, dec!("500000"), &mut env.test_runner);
+    let (token_component, _token_dapp_def, token_address) =
+        utils::token::create_token_curve_component_advanced(
+            String::from("Offset Token"),
+            String::from("OFFSET"),
+            String::from("A token on a quadratic bonding curve with a starting price"),
+            String::from("https://dexteronradix.com/dexter-logo-and-lettering.svg"),
+            String::from(""),
+            String::from(""),
+            String::from("https://radix.meme"),
+            BondingCurveConfig::Power {
+                exponent: dec!("2"),
+                initial_price: dec!("0.1"),
+            },
+            None,
+            env.token_creation_fee.clone(),
+            &env.parent_component_address,
+            &env.owner_account,
+            &mut env.test_runner,
+        );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    // max xrd is initial_price * max_supply + (end_price - initial_price) * max_supply / 3
+    assert!(
+        token_state.max_xrd == dec!("400000"),
+        "Incorrect max xrd for power curve with initial price. {:?}",
+        token_state.max_xrd
+    );
+
+    // the first buyer pays at least the initial price for every token
+    let _buy_receipt = utils::txs::token_buy(
+        dec!(100),
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    assert!(
+        token_state.current_supply > dec!("999") && token_state.current_supply < dec!("1000"),
+        "Incorrect supply after buy on power curve with initial price. {:?}",
+        token_state.current_supply
+    );
+    assert!(
+        token_state.last_price > dec!("0.1") && token_state.last_price < dec!("0.100001"),
+        "Incorrect price after buy on power curve with initial price. {:?}",
+        token_state.last_price
+    );
+
+    let token_balance = env
+        .test_runner
+        .get_component_balance(env.owner_account.address.clone(), token_address);
+    let _sell_receipt = utils::txs::token_sell_for_xrd_amount(
+        dec!("50"),
+        token_balance,
+        &token_address,
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    assert!(
+        token_state.current_supply > dec!("499") && token_state.current_supply < dec!("500"),
+        "Incorrect supply after sell for xrd amount on power curve with initial price. {:?}",
+        token_state.current_supply
+    );
+    let component_xrd_balance = env.test_runner.get_component_balance(token_component, XRD);
+    assert!(
+        component_xrd_balance == dec!("50"),
+        "Incorrect XRD in component after sell for xrd amount. {:?}",
+        component_xrd_balance
+    );
+
+    // buying out the curve still ends exactly at the max market cap
+    let _buy_all_receipt = utils::txs::token_buy(
+        dec!(500000),
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    assert!(
+        token_state.current_supply == dec!("1000000"),
+        "Incorrect supply after buying all tokens on power curve with initial price. {:?}",
+        token_state.current_supply
+    );
+    assert!(
+        token_state.last_price == dec!("1"),
+        "Incorrect price after buying all tokens on power curve with initial price. {:?}",
+        token_state.last_price
+    );
+}
+
+#[test]
+fn quote_tests() {
+    let mut env = utils::setup_test_env(0, true);
+    let buy_quote = utils::txs::token_quote(
+        "quote_buy",
+        dec!(100),
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        buy_quote.fee_amount == dec!("1") && buy_quote.xrd_amount == dec!("99"),
+        "Incorrect fee split in buy quote. {:?}",
+        buy_quote
+    );
+    assert!(
+        !buy_quote.target_reached,
+        "Buy quote should not reach target."
+    );
+    let _buy_receipt = utils::txs::token_buy(
+        dec!(100),
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&env.token1_component, &mut env.test_runner);
+    assert!(
+        token_state.current_supply == buy_quote.token_amount,
+        "Supply after buy does not match quote. {:?}",
+        token_state.current_supply
+    );
+    assert!(
+        token_state.last_price == buy_quote.end_price,
+        "Price after buy does not match quote. {:?}",
+        token_state.last_price
+    );
+
+    let buy_amount_quote = utils::txs::token_quote(
+        "quote_buy_amount",
+        dec!(1000),
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    let xrd_balance_before = env
+        .test_runner
+        .get_component_balance(env.owner_account.address.clone(), XRD);
+    let _buy_amount_receipt = utils::txs::token_buy_amount(
+        dec!(1000),
+        dec!(100),
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    let xrd_balance_after = env
         .test_runner
         .get_component_balance(env.owner_account.address.clone(), XRD);
     assert!(
-        xrd_balance == dec!("510000"),
-        "Incorrect XRD Balance in account after first buy. {:?}",
-        xrd_balance.clone()
+        xrd_balance_before - xrd_balance_after
+            == buy_amount_quote.xrd_amount + buy_amount_quote.fee_amount,
+        "XRD paid for buy amount does not match quote. {:?}",
+        xrd_balance_before - xrd_balance_after
+    );
+
+    let token_balance = env.test_runner.get_component_balance(
+        env.owner_account.address.clone(),
+        env.token1_address.clone(),
+    );
+    let sell_quote = utils::txs::token_quote(
+        "quote_sell",
+        token_balance,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    let _sell_receipt = utils::txs::token_sell(
+        token_balance,
+        &env.token1_address,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    let xrd_balance_after_sell = env
+        .test_runner
+        .get_component_balance(env.owner_account.address.clone(), XRD);
+    assert!(
+        xrd_balance_after_sell - xrd_balance_after == sell_quote.xrd_amount,
+        "XRD received for sell does not match quote. {:?}",
+        xrd_balance_after_sell - xrd_balance_after
+    );
+    let token_state = utils::token::get_token_state(&env.token1_component, &mut env.test_runner);
+    assert!(
+        token_state.last_price == sell_quote.end_price,
+        "Price after sell does not match quote. {:?}",
+        token_state.last_price
+    );
+}
+
+#[test]
+fn slippage_tests() {
+    let mut env = utils::setup_test_env(0, true);
+    let buy_quote = utils::txs::token_quote(
+        "quote_buy",
+        dec!(100),
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    let failed_buy_receipt = utils::txs::token_buy_with_limit(
+        dec!(100),
+        Some(buy_quote.token_amount + dec!("0.000001")),
+        None,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        failed_buy_receipt.is_commit_failure(),
+        "Buy should fail when min_tokens_out is not met."
+    );
+    utils::txs::token_buy_with_limit(
+        dec!(100),
+        Some(buy_quote.token_amount),
+        None,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+
+    let buy_amount_quote = utils::txs::token_quote(
+        "quote_buy_amount",
+        dec!(1000),
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    let max_xrd = buy_amount_quote.xrd_amount + buy_amount_quote.fee_amount;
+    let failed_buy_amount_receipt = utils::txs::token_buy_amount_with_limit(
+        dec!(1000),
+        dec!(100),
+        Some(max_xrd - dec!("0.000001")),
+        None,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        failed_buy_amount_receipt.is_commit_failure(),
+        "Buy amount should fail when max_xrd_in is exceeded."
+    );
+    utils::txs::token_buy_amount_with_limit(
+        dec!(1000),
+        dec!(100),
+        Some(max_xrd),
+        None,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+
+    let sell_quote = utils::txs::token_quote(
+        "quote_sell",
+        dec!(1000),
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    let failed_sell_receipt = utils::txs::token_sell_with_limit(
+        dec!(1000),
+        Some(sell_quote.xrd_amount + dec!("0.000001")),
+        None,
+        &env.token1_address,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        failed_sell_receipt.is_commit_failure(),
+        "Sell should fail when min_xrd_out is not met."
+    );
+    utils::txs::token_sell_with_limit(
+        dec!(1000),
+        Some(sell_quote.xrd_amount),
+        None,
+        &env.token1_address,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+
+    let sell_for_xrd_quote = utils::txs::token_quote(
+        "quote_sell_for_xrd",
+        dec!(1),
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    let token_balance = env.test_runner.get_component_balance(
+        env.owner_account.address.clone(),
+        env.token1_address.clone(),
+    );
+    let failed_sell_for_xrd_receipt = utils::txs::token_sell_for_xrd_amount_with_limit(
+        dec!(1),
+        token_balance,
+        Some(sell_for_xrd_quote.token_amount - dec!("0.000001")),
+        None,
+        &env.token1_address,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        failed_sell_for_xrd_receipt.is_commit_failure(),
+        "Sell for XRD should fail when max_tokens_in is exceeded."
+    );
+    utils::txs::token_sell_for_xrd_amount_with_limit(
+        dec!(1),
+        token_balance,
+        Some(sell_for_xrd_quote.token_amount),
+        None,
+        &env.token1_address,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+}
+
+#[test]
+fn deadline_tests() {
+    let mut env = utils::setup_test_env(0, true);
+    env.test_runner
+        .advance_to_round_at_timestamp(Round::of(1), 1_000_000_000);
+    let failed_buy_receipt = utils::txs::token_buy_with_limit(
+        dec!(100),
+        None,
+        Some(999_999),
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    failed_buy_receipt
+        .expect_specific_failure(|error| format!("{:?}", error).contains("Trade deadline expired"));
+    utils::txs::token_buy_with_limit(
+        dec!(100),
+        None,
+        Some(1_000_000),
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+
+    let failed_sell_receipt = utils::txs::token_sell_with_limit(
+        dec!(1000),
+        None,
+        Some(999_999),
+        &env.token1_address,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    failed_sell_receipt
+        .expect_specific_failure(|error| format!("{:?}", error).contains("Trade deadline expired"));
+    utils::txs::token_sell_with_limit(
+        dec!(1000),
+        None,
+        Some(1_000_060),
+        &env.token1_address,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+}
+
+#[test]
+fn curve_math_native_tests() {
+    let mut env = utils::setup_test_env(0, true);
+    let _buy_receipt = utils::txs::token_buy(
+        dec!(100),
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&env.token1_component, &mut env.test_runner);
+    let curve_state = CurveState {
+        current_supply: token_state.current_supply,
+        xrd_reserve: env
+            .test_runner
+            .inspect_vault_balance(token_state.xrd_vault.0 .0)
+            .expect("Could not read token curve xrd vault"),
+        max_xrd: token_state.max_xrd,
+        max_token_supply_to_trade: token_state.max_token_supply_to_trade,
+        tx_fee_perc: token_state.tx_fee_perc,
+        fee_discount: Decimal::ZERO,
+    };
+
+    let native_quotes = vec![
+        (
+            "quote_buy",
+            dec!(50),
+            curve_math::calculate_buy(&token_state.bonding_curve, &curve_state, dec!(50)),
+        ),
+        (
+            "quote_buy_amount",
+            dec!(1000),
+            curve_math::calculate_buy_amount(&token_state.bonding_curve, &curve_state, dec!(1000)),
+        ),
+        (
+            "quote_sell",
+            dec!(1000),
+            curve_math::calculate_sell(&token_state.bonding_curve, &curve_state, dec!(1000)),
+        ),
+        (
+            "quote_sell_for_xrd",
+            dec!(10),
+            curve_math::calculate_sell_for_xrd_amount(
+                &token_state.bonding_curve,
+                &curve_state,
+                dec!(10),
+            ),
+        ),
+    ];
+    for (method_name, amount, native_quote) in native_quotes {
+        let ledger_quote = utils::txs::token_quote(
+            method_name,
+            amount,
+            &env.token1_component,
+            &mut env.test_runner,
+        );
+        assert!(
+            native_quote.token_amount == ledger_quote.token_amount
+                && native_quote.xrd_amount == ledger_quote.xrd_amount
+                && native_quote.fee_amount == ledger_quote.fee_amount
+                && native_quote.end_price == ledger_quote.end_price,
+            "Native curve math does not match ledger for {}. Native: {:?}, Ledger: {:?}",
+            method_name,
+            native_quote,
+            ledger_quote
+        );
+    }
+}
+
+#[test]
+fn creator_fee_tests() {
+    let mut env = utils::setup_test_env(0, true);
+    utils::parent::change_default_parameter(
+        "creator_fee_share",
+        "0.5",
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    let creator_account = utils::create_new_account(&mut env.test_runner);
+    let (token_component, _dapp_def, _token_address) = utils::token::create_token_curve_component(
+        String::from("Creator Token"),
+        String::from("CREATOR"),
+        String::from("A token with a creator fee share"),
+        String::from("https://radix.meme"),
+        String::from(""),
+        String::from(""),
+        String::from(""),
+        env.token_creation_fee.clone(),
+        &env.parent_component_address,
+        &creator_account,
+        &mut env.test_runner,
+    );
+    let _buy_receipt = utils::txs::token_buy(
+        dec!(100),
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    assert!(
+        token_state.creator_fee_share == dec!("0.5"),
+        "Incorrect creator fee share on token. {:?}",
+        token_state.creator_fee_share
+    );
+
+    let failed_claim_receipt = utils::token::claim_creator_fees(
+        &token_component,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    assert!(
+        failed_claim_receipt.is_commit_failure(),
+        "Only the token creator should be able to claim creator fees."
+    );
+
+    let xrd_balance_before = env
+        .test_runner
+        .get_component_balance(creator_account.address.clone(), XRD);
+    utils::token::claim_creator_fees(&token_component, &creator_account, &mut env.test_runner)
+        .expect_commit_success();
+    let xrd_balance_after = env
+        .test_runner
+        .get_component_balance(creator_account.address.clone(), XRD);
+    assert!(
+        xrd_balance_after - xrd_balance_before == dec!("0.5"),
+        "Incorrect creator fees claimed. {:?}",
+        xrd_balance_after - xrd_balance_before
+    );
+    assert!(
+        env.test_runner
+            .get_component_balance(token_component.clone(), XRD)
+            == dec!("99.5"),
+        "Incorrect XRD left in token component after creator fee claim."
+    );
+}
+
+#[test]
+fn fee_forward_tests() {
+    let mut env = utils::setup_test_env(0, true);
+    utils::parent::change_default_parameter(
+        "fee_forward_threshold",
+        "2",
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    let (token_component, _dapp_def, _token_address) = utils::token::create_token_curve_component(
+        String::from("Forward Token"),
+        String::from("FORWARD"),
+        String::from("A token that forwards its fees"),
+        String::from("https://radix.meme"),
+        String::from(""),
+        String::from(""),
+        String::from(""),
+        env.token_creation_fee.clone(),
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    let parent_balance_before = env
+        .test_runner
+        .get_component_balance(env.parent_component_address.clone(), XRD);
+    let _first_buy_receipt = utils::txs::token_buy(
+        dec!(100),
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        env.test_runner
+            .get_component_balance(env.parent_component_address.clone(), XRD)
+            == parent_balance_before,
+        "Fees should not be forwarded before the threshold is reached."
+    );
+    let _second_buy_receipt = utils::txs::token_buy(
+        dec!(100),
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    let parent_balance_after = env
+        .test_runner
+        .get_component_balance(env.parent_component_address.clone(), XRD);
+    assert!(
+        parent_balance_after - parent_balance_before == dec!("2"),
+        "Incorrect fees forwarded to parent. {:?}",
+        parent_balance_after - parent_balance_before
+    );
+    assert!(
+        env.test_runner
+            .get_component_balance(token_component.clone(), XRD)
+            == dec!("198"),
+        "Token component should only hold the curve XRD after forwarding fees."
+    );
+}
+
+#[test]
+fn sweep_fees_tests() {
+    let mut env = utils::setup_test_env(0, true);
+    let (token2_component, _dapp_def, _token2_address) = utils::token::create_token_curve_component(
+        String::from("Second Token"),
+        String::from("SECOND"),
+        String::from("The second token on Radix Meme Tokens"),
+        String::from("https://radix.meme"),
+        String::from(""),
+        String::from(""),
+        String::from(""),
+        env.token_creation_fee.clone(),
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    for token_component in [env.token1_component.clone(), token2_component.clone()] {
+        let _buy_receipt = utils::txs::token_buy(
+            dec!(100),
+            &env.owner_account,
+            &token_component,
+            &mut env.test_runner,
+        );
+    }
+    let parent_state =
+        utils::parent::get_parent_state(&env.parent_component_address, &mut env.test_runner);
+    assert!(
+        parent_state.token_count == 2,
+        "Incorrect token count on parent. {:?}",
+        parent_state.token_count
+    );
+
+    let parent_balance_before = env
+        .test_runner
+        .get_component_balance(env.parent_component_address.clone(), XRD);
+    utils::parent::sweep_fees(
+        0,
+        1,
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    let parent_balance_after_first = env
+        .test_runner
+        .get_component_balance(env.parent_component_address.clone(), XRD);
+    assert!(
+        parent_balance_after_first - parent_balance_before == dec!("1"),
+        "Only the first token's fees should be swept. {:?}",
+        parent_balance_after_first - parent_balance_before
+    );
+    assert!(
+        env.test_runner
+            .get_component_balance(token2_component.clone(), XRD)
+            == dec!("100"),
+        "Second token fees should not be swept yet."
+    );
+
+    // u64::MAX sweeps all the remaining components
+    utils::parent::sweep_fees(
+        1,
+        u64::MAX,
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    let parent_balance_after_all = env
+        .test_runner
+        .get_component_balance(env.parent_component_address.clone(), XRD);
+    assert!(
+        parent_balance_after_all - parent_balance_before == dec!("2"),
+        "Incorrect total fees swept. {:?}",
+        parent_balance_after_all - parent_balance_before
+    );
+}
+
+#[test]
+fn fee_schedule_tests() {
+    let mut env = utils::setup_test_env(0, true);
+    utils::parent::change_default_fee_schedule(
+        Some(FeeSchedule {
+            start_fee_perc: dec!("0.1"),
+            end_fee_perc: dec!("0.01"),
+            decay_period_mins: 10,
+            decay_steps: 0,
+        }),
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    let (token_component, _dapp_def, _token_address) = utils::token::create_token_curve_component(
+        String::from("Schedule Token"),
+        String::from("SCHEDULE"),
+        String::from("A token with a launch fee schedule"),
+        String::from("https://radix.meme"),
+        String::from(""),
+        String::from(""),
+        String::from(""),
+        env.token_creation_fee.clone(),
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    let time_created =
+        utils::token::get_token_state(&token_component, &mut env.test_runner).time_created;
+
+    let start_quote = utils::txs::token_quote(
+        "quote_buy",
+        dec!(100),
+        &token_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        start_quote.fee_perc == dec!("0.1") && start_quote.fee_amount == dec!("10"),
+        "Incorrect fee at start of fee schedule. {:?}",
+        start_quote
+    );
+
+    env.test_runner
+        .advance_to_round_at_timestamp(Round::of(1), (time_created + 300) * 1000);
+    let halfway_quote = utils::txs::token_quote(
+        "quote_buy",
+        dec!(100),
+        &token_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        halfway_quote.fee_perc == dec!("0.055") && halfway_quote.fee_amount == dec!("5.5"),
+        "Incorrect fee halfway through fee schedule. {:?}",
+        halfway_quote
+    );
+    let _buy_receipt = utils::txs::token_buy(
+        dec!(100),
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        env.test_runner
+            .get_component_balance(token_component.clone(), XRD)
+            == dec!("100"),
+        "Incorrect XRD in token component after buy with scheduled fee."
+    );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    assert!(
+        token_state.current_supply == halfway_quote.token_amount,
+        "Buy did not use the scheduled fee. {:?}",
+        token_state.current_supply
+    );
+
+    env.test_runner
+        .advance_to_round_at_timestamp(Round::of(2), (time_created + 600) * 1000);
+    let end_quote = utils::txs::token_quote(
+        "quote_sell",
+        dec!(1000),
+        &token_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        end_quote.fee_perc == dec!("0.01"),
+        "Incorrect fee after fee schedule ended. {:?}",
+        end_quote
+    );
+}
+
+#[test]
+fn pass_fee_discount_tests() {
+    let mut env = utils::setup_test_env(0, true);
+    let pass_address =
+        env.test_runner
+            .create_fungible_resource(dec!(10), 0, env.owner_account.address.clone());
+    let other_resource =
+        env.test_runner
+            .create_fungible_resource(dec!(10), 0, env.owner_account.address.clone());
+    utils::parent::change_pass_fee_discounts(
+        Some(pass_address.clone()),
+        vec![(dec!(1), dec!("0.25")), (dec!(5), dec!("0.5"))],
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+
+    let wrong_pass_receipt = utils::txs::token_buy_with_pass(
+        dec!(100),
+        &other_resource,
+        dec!(5),
+        None,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        wrong_pass_receipt.is_commit_failure(),
+        "Buy with the wrong pass resource should fail."
+    );
+
+    utils::txs::token_buy_with_pass(
+        dec!(100),
+        &pass_address,
+        dec!(5),
+        None,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    let token_state = utils::token::get_token_state(&env.token1_component, &mut env.test_runner);
+    let fee_balance = env
+        .test_runner
+        .inspect_vault_balance(token_state.fee_vault.0 .0)
+        .expect("Could not read token curve fee vault");
+    assert!(
+        fee_balance == dec!("0.5"),
+        "Incorrect discounted fee for top pass tier. {:?}",
+        fee_balance
+    );
+
+    utils::txs::token_buy_with_pass(
+        dec!(100),
+        &pass_address,
+        dec!(1),
+        None,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    let token_state = utils::token::get_token_state(&env.token1_component, &mut env.test_runner);
+    let fee_balance = env
+        .test_runner
+        .inspect_vault_balance(token_state.fee_vault.0 .0)
+        .expect("Could not read token curve fee vault");
+    assert!(
+        fee_balance == dec!("1.25"),
+        "Incorrect discounted fee for first pass tier. {:?}",
+        fee_balance
+    );
+}
+
+#[test]
+fn referral_fee_tests() {
+    let mut env = utils::setup_test_env(0, true);
+    utils::parent::change_default_parameter(
+        "referral_fee_share",
+        "0.5",
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    let referrer_account = utils::create_new_account(&mut env.test_runner);
+    utils::parent::register_referrer(
+        "Integrator",
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    let parent_state =
+        utils::parent::get_parent_state(&env.parent_component_address, &mut env.test_runner);
+    let referrer_badge_address = parent_state.referrer_badge_manager.address();
+    let transfer_manifest = ManifestBuilder::new()
+        .call_method(
+            env.owner_account.address.clone(),
+            "withdraw",
+            manifest_args![referrer_badge_address.clone(), dec!(1)],
+        )
+        .try_deposit_entire_worktop_or_abort(referrer_account.address, None)
+        .build();
+    env.test_runner
+        .execute_manifest_ignoring_fee(
+            transfer_manifest,
+            vec![NonFungibleGlobalId::from_public_key(
+                &env.owner_account.pubkey,
+            )],
+        )
+        .expect_commit_success();
+
+    let unknown_referrer_receipt = utils::txs::token_buy_with_referrer(
+        dec!(100),
+        NonFungibleLocalId::integer(2),
+        None,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        unknown_referrer_receipt.is_commit_failure(),
+        "Buy with an unregistered referrer should fail."
+    );
+    utils::txs::token_buy_with_referrer(
+        dec!(100),
+        NonFungibleLocalId::integer(1),
+        None,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    assert!(
+        env.test_runner
+            .get_component_balance(env.token1_component.clone(), XRD)
+            == dec!("99.5"),
+        "Referral share of the fee should be paid out of the token component."
+    );
+    // only registered token curves can deposit referral fees, so referral fee events cannot be faked
+    let direct_deposit_receipt = utils::parent::deposit_referral_fee(
+        &env.token1_component,
+        NonFungibleLocalId::integer(1),
+        dec!(100),
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    assert!(
+        direct_deposit_receipt.is_commit_failure(),
+        "Referral fees should only be deposited by the token curve itself."
+    );
+    let unregistered_deposit_receipt = utils::parent::deposit_referral_fee(
+        &env.owner_account.address,
+        NonFungibleLocalId::integer(1),
+        dec!(100),
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    assert!(
+        unregistered_deposit_receipt.is_commit_failure(),
+        "Referral fees should only be deposited by registered token curves."
+    );
+
+    let xrd_balance_before = env
+        .test_runner
+        .get_component_balance(referrer_account.address.clone(), XRD);
+    utils::parent::claim_referral_fees(
+        &referrer_badge_address,
+        &env.parent_component_address,
+        &referrer_account,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    let xrd_balance_after = env
+        .test_runner
+        .get_component_balance(referrer_account.address.clone(), XRD);
+    assert!(
+        xrd_balance_after - xrd_balance_before == dec!("0.5"),
+        "Incorrect referral fees claimed. {:?}",
+        xrd_balance_after - xrd_balance_before
+    );
+}
+
+#[test]
+fn graduation_tests() {
+    let mut env = utils::setup_test_env(0, true);
+    utils::load_account_with_xrd(&env.owner_account, dec!("500000"), &mut env.test_runner);
+    let parent_xrd_before = env
+        .test_runner
+        .get_component_balance(env.parent_component_address.clone(), XRD);
+
+    let _buy_receipt = utils::txs::token_buy(
+        dec!(500000),
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&env.token1_component, &mut env.test_runner);
+    assert!(
+        token_state.status == CurveStatus::Graduated,
+        "Token should be graduated after reaching target. {:?}",
+        token_state.status
+    );
+    assert!(
+        token_state.target_reached > 0,
+        "Target reached date should be set after graduation."
+    );
+
+    let xrd_raised = dec!("333333.333333333333333333");
+    let listing_fee = xrd_raised * env.listing_fee_perc;
+    let creator_fee = xrd_raised * env.creator_fee_perc;
+    let parent_xrd_after = env
+        .test_runner
+        .get_component_balance(env.parent_component_address.clone(), XRD);
+    assert!(
+        parent_xrd_after - parent_xrd_before == listing_fee,
+        "Only the listing fee should be paid to the parent. {:?}",
+        parent_xrd_after - parent_xrd_before
+    );
+    let creator_fee_balance = env
+        .test_runner
+        .inspect_vault_balance(token_state.creator_fee_vault.0 .0)
+        .unwrap();
+    assert!(
+        creator_fee_balance == creator_fee,
+        "Incorrect creator fee after graduation. {:?}",
+        creator_fee_balance
+    );
+    let xrd_remaining = env
+        .test_runner
+        .inspect_vault_balance(token_state.xrd_vault.0 .0)
+        .unwrap();
+    assert!(
+        xrd_remaining == Decimal::ZERO,
+        "No XRD should remain on the curve after graduation. {:?}",
+        xrd_remaining
+    );
+}
+
+#[test]
+fn graduation_pool_tests() {
+    let mut env = utils::setup_test_env(0, true);
+    let (token_component, _dapp_def, token_address) = utils::token::create_token_curve_component(
+        String::from("Pool Token"),
+        String::from("POOL"),
+        String::from("A token that is listed on a pool at graduation"),
+        String::from("https://radix.meme"),
+        String::from(""),
+        String::from(""),
+        String::from(""),
+        env.token_creation_fee.clone(),
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    utils::load_account_with_xrd(&env.owner_account, dec!("500000"), &mut env.test_runner);
+    let _buy_receipt = utils::txs::token_buy(
+        dec!(500000),
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    assert!(
+        token_state.status == CurveStatus::Graduated,
+        "Token should be graduated after reaching target. {:?}",
+        token_state.status
+    );
+    let pool_component = token_state
+        .pool_address
+        .expect("Pool should be created when the token graduates.");
+    let lp_lock = token_state
+        .lp_lock_address
+        .expect("LP tokens should be deposited into an LP lock after graduation.");
+
+    let xrd_raised = dec!("333333.333333333333333333");
+    let pool_xrd =
+        xrd_raised - xrd_raised * env.listing_fee_perc - xrd_raised * env.creator_fee_perc;
+    let pool_state = utils::pool::get_pool_state(&pool_component, &mut env.test_runner);
+    let xrd_reserve = env
+        .test_runner
+        .inspect_vault_balance(pool_state.xrd_vault.0 .0)
+        .unwrap();
+    let token_reserve = env
+        .test_runner
+        .inspect_vault_balance(pool_state.token_vault.0 .0)
+        .unwrap();
+    assert!(
+        xrd_reserve == pool_xrd,
+        "Incorrect XRD seeded into pool. {:?}",
+        xrd_reserve
+    );
+    // the pool opens at the final price on the curve, so there is no price jump when trading moves to the pool
+    assert!(
+        token_reserve == xrd_reserve / token_state.last_price
+            && (xrd_reserve / token_reserve - token_state.last_price)
+                .checked_abs()
+                .unwrap()
+                < dec!("0.000001"),
+        "Pool price should match the final curve price. Tokens: {:?}, XRD: {:?}, last price: {:?}",
+        token_reserve,
+        xrd_reserve,
+        token_state.last_price
+    );
+    let lp_balance = utils::lp_lock::get_lp_lock_balance(&lp_lock, &mut env.test_runner);
+    assert!(
+        lp_balance > Decimal::ZERO
+            && env
+                .test_runner
+                .get_component_balance(token_component.clone(), pool_state.lp_manager.address())
+                == Decimal::ZERO,
+        "All LP tokens should be held by the LP lock and none by the token curve. {:?}",
+        lp_balance
+    );
+    let token_curve_xrd = env
+        .test_runner
+        .inspect_vault_balance(token_state.xrd_vault.0 .0)
+        .unwrap();
+    assert!(
+        token_curve_xrd == Decimal::ZERO,
+        "All remaining XRD should be moved to the pool. {:?}",
+        token_curve_xrd
+    );
+
+    let curve_buy_receipt = utils::txs::token_buy_with_limit(
+        dec!(1000),
+        None,
+        None,
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        curve_buy_receipt.is_commit_failure(),
+        "Curve trades should be rejected after graduation when routing is switched off."
+    );
+
+    let token_balance_before = env
+        .test_runner
+        .get_component_balance(env.owner_account.address.clone(), token_address.clone());
+    utils::pool::pool_swap(
+        &XRD,
+        dec!(1000),
+        &env.owner_account,
+        &pool_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    let tokens_received = env
+        .test_runner
+        .get_component_balance(env.owner_account.address.clone(), token_address.clone())
+        - token_balance_before;
+    let xrd_in = dec!(1000) - dec!(1000) * pool_state.fee_perc;
+    let expected_tokens = token_reserve * xrd_in / (xrd_reserve + xrd_in);
+    assert!(
+        (tokens_received - expected_tokens).checked_abs().unwrap() < dec!("0.000001"),
+        "Incorrect tokens received from pool swap. {:?} Expected: {:?}",
+        tokens_received,
+        expected_tokens
+    );
+
+    let lp_address = pool_state.lp_manager.address();
+    utils::pool::pool_add_liquidity(
+        dec!(1000),
+        &token_address,
+        dec!(10000),
+        &env.owner_account,
+        &pool_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    let lp_received = env
+        .test_runner
+        .get_component_balance(env.owner_account.address.clone(), lp_address.clone());
+    assert!(
+        lp_received > Decimal::ZERO,
+        "No LP tokens received for added liquidity."
+    );
+    let xrd_balance_before = env
+        .test_runner
+        .get_component_balance(env.owner_account.address.clone(), XRD);
+    utils::pool::pool_remove_liquidity(
+        &lp_address,
+        lp_received,
+        &env.owner_account,
+        &pool_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    let xrd_returned = env
+        .test_runner
+        .get_component_balance(env.owner_account.address.clone(), XRD)
+        - xrd_balance_before;
+    assert!(
+        xrd_returned <= dec!(1000) && dec!(1000) - xrd_returned < dec!("0.000001"),
+        "Incorrect XRD returned when removing liquidity. {:?}",
+        xrd_returned
+    );
+}
+
+#[test]
+fn dex_adapter_tests() {
+    let mut env = utils::setup_test_env(0, true);
+    let adapter = utils::parent::create_mock_dex_adapter(
+        &env.package_address,
+        &env.owner_badge_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    let unregistered_receipt = utils::parent::change_default_dex_adapter(
+        Some(adapter.clone()),
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    assert!(
+        unregistered_receipt.is_commit_failure(),
+        "Only registered DEX adapters can be set as the default."
+    );
+    utils::parent::register_dex_adapter(
+        &adapter,
+        "Mock DEX",
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    utils::parent::change_default_dex_adapter(
+        Some(adapter.clone()),
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+
+    let (token_component, _dapp_def, _token_address) = utils::token::create_token_curve_component(
+        String::from("Adapter Token"),
+        String::from("ADAPT"),
+        String::from("A token that is listed through a DEX adapter"),
+        String::from("https://radix.meme"),
+        String::from(""),
+        String::from(""),
+        String::from(""),
+        env.token_creation_fee.clone(),
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    assert!(
+        token_state.dex_adapter == Some(adapter.clone()),
+        "Token should use the default DEX adapter. {:?}",
+        token_state.dex_adapter
+    );
+
+    utils::load_account_with_xrd(&env.owner_account, dec!("500000"), &mut env.test_runner);
+    let _buy_receipt = utils::txs::token_buy(
+        dec!(500000),
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    assert!(
+        token_state.status == CurveStatus::Graduated,
+        "Token should be graduated after reaching target. {:?}",
+        token_state.status
+    );
+    assert!(
+        token_state.pool_address == Some(adapter.clone()),
+        "Token should be listed through the DEX adapter. {:?}",
+        token_state.pool_address
+    );
+    let xrd_raised = dec!("333333.333333333333333333");
+    let listed_xrd =
+        xrd_raised - xrd_raised * env.listing_fee_perc - xrd_raised * env.creator_fee_perc;
+    let lp_balance = utils::lp_lock::get_lp_lock_balance(
+        &token_state.lp_lock_address.unwrap(),
+        &mut env.test_runner,
+    );
+    assert!(
+        lp_balance == listed_xrd,
+        "Incorrect LP tokens received from DEX adapter. {:?}",
+        lp_balance
+    );
+}
+
+#[test]
+fn dex_adapter_unused_xrd_tests() {
+    let mut env = utils::setup_test_env(0, true);
+    let adapter = utils::parent::create_mock_dex_adapter(
+        &env.package_address,
+        &env.owner_badge_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    utils::parent::set_mock_dex_unused_xrd_perc(
+        dec!("0.2"),
+        &env.owner_badge_address,
+        &adapter,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    utils::parent::register_dex_adapter(
+        &adapter,
+        "Mock DEX",
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    utils::parent::change_default_dex_adapter(
+        Some(adapter.clone()),
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    let (token_component, _dapp_def, _token_address) = utils::token::create_token_curve_component(
+        String::from("Adapter Token"),
+        String::from("ADAPT"),
+        String::from("A token that is listed through a DEX adapter"),
+        String::from("https://radix.meme"),
+        String::from(""),
+        String::from(""),
+        String::from(""),
+        env.token_creation_fee.clone(),
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+
+    utils::load_account_with_xrd(&env.owner_account, dec!("500000"), &mut env.test_runner);
+    let parent_xrd_before = env
+        .test_runner
+        .get_component_balance(env.parent_component_address.clone(), XRD);
+    let _buy_receipt = utils::txs::token_buy(
+        dec!(500000),
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    assert!(
+        token_state.status == CurveStatus::Graduated,
+        "Token should be graduated after reaching target. {:?}",
+        token_state.status
+    );
+    let xrd_raised = dec!("333333.333333333333333333");
+    let listing_fee = xrd_raised * env.listing_fee_perc;
+    let listed_xrd = xrd_raised - listing_fee - xrd_raised * env.creator_fee_perc;
+    let unused_xrd = listed_xrd * dec!("0.2");
+    let lp_balance = utils::lp_lock::get_lp_lock_balance(
+        &token_state.lp_lock_address.unwrap(),
+        &mut env.test_runner,
+    );
+    assert!(
+        lp_balance == listed_xrd - unused_xrd,
+        "LP tokens should only be received for the XRD used by the DEX adapter. {:?}",
+        lp_balance
+    );
+    let token_curve_xrd = env
+        .test_runner
+        .inspect_vault_balance(token_state.xrd_vault.0 .0)
+        .unwrap();
+    assert!(
+        token_curve_xrd == Decimal::ZERO,
+        "Unused XRD should not stay on the graduated curve. {:?}",
+        token_curve_xrd
+    );
+    let parent_xrd_after = env
+        .test_runner
+        .get_component_balance(env.parent_component_address.clone(), XRD);
+    assert!(
+        parent_xrd_after - parent_xrd_before == listing_fee + unused_xrd,
+        "Unused XRD should be sent to the parent with the listing fee. {:?}",
+        parent_xrd_after - parent_xrd_before
+    );
+}
+
+#[test]
+fn lp_token_lock_tests() {
+    let mut env = utils::setup_test_env(0, true);
+    let locked_token_component = utils::token::create_token_curve_component(
+        String::from("Locked Token"),
+        String::from("LOCKED"),
+        String::from("A token that locks its LP tokens forever at graduation"),
+        String::from("https://radix.meme"),
+        String::from(""),
+        String::from(""),
+        String::from(""),
+        env.token_creation_fee.clone(),
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    )
+    .0;
+    utils::parent::change_default_lp_token_policy(
+        LpTokenPolicy::TimeLock {
+            lock_period_mins: 60,
+        },
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    let lock_token_component = utils::token::create_token_curve_component(
+        String::from("Lock Token"),
+        String::from("LOCK"),
+        String::from("A token that time locks its LP tokens at graduation"),
+        String::from("https://radix.meme"),
+        String::from(""),
+        String::from(""),
+        String::from(""),
+        env.token_creation_fee.clone(),
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    )
+    .0;
+
+    utils::load_account_with_xrd(&env.owner_account, dec!("1000000"), &mut env.test_runner);
+    for token_component in [locked_token_component.clone(), lock_token_component.clone()] {
+        let _buy_receipt = utils::txs::token_buy(
+            dec!(500000),
+            &env.owner_account,
+            &token_component,
+            &mut env.test_runner,
+        );
+    }
+
+    let locked_token_state =
+        utils::token::get_token_state(&locked_token_component, &mut env.test_runner);
+    assert!(
+        locked_token_state.lp_token_policy == LpTokenPolicy::LockForever,
+        "LockForever should be the default LP token policy."
+    );
+    assert!(
+        locked_token_state.lp_unlock_time.is_none(),
+        "LP tokens locked forever should not have an unlock time."
+    );
+    let locked_lp_lock = locked_token_state
+        .lp_lock_address
+        .expect("LP tokens locked forever should be deposited into an LP lock.");
+    let locked_lp_amount =
+        utils::lp_lock::get_lp_lock_balance(&locked_lp_lock, &mut env.test_runner);
+    assert!(
+        locked_lp_amount > Decimal::ZERO,
+        "No LP tokens locked forever."
+    );
+    let locked_withdraw_receipt = utils::lp_lock::withdraw_lp_tokens(
+        &env.owner_badge_address,
+        &locked_lp_lock,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    assert!(
+        locked_withdraw_receipt.is_commit_failure(),
+        "LP tokens locked forever should never be withdrawable."
+    );
+
+    let lock_token_state =
+        utils::token::get_token_state(&lock_token_component, &mut env.test_runner);
+    let unlock_time = lock_token_state
+        .lp_unlock_time
+        .expect("Time locked LP tokens should have an unlock time.");
+    assert!(
+        unlock_time == lock_token_state.target_reached + 3600,
+        "Incorrect LP token unlock time. {:?}",
+        unlock_time
+    );
+    let lp_lock = lock_token_state
+        .lp_lock_address
+        .expect("Time locked LP tokens should be deposited into an LP lock.");
+    let lp_lock_state = utils::lp_lock::get_lp_lock_state(&lp_lock, &mut env.test_runner);
+    assert!(
+        lp_lock_state.unlock_time == Some(unlock_time),
+        "Incorrect unlock time on LP lock. {:?}",
+        lp_lock_state.unlock_time
+    );
+    let lp_amount = utils::lp_lock::get_lp_lock_balance(&lp_lock, &mut env.test_runner);
+    assert!(lp_amount > Decimal::ZERO, "No LP tokens locked.");
+    let early_withdraw_receipt = utils::lp_lock::withdraw_lp_tokens(
+        &env.owner_badge_address,
+        &lp_lock,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    assert!(
+        early_withdraw_receipt.is_commit_failure(),
+        "LP tokens should not be withdrawable before the unlock time."
+    );
+
+    env.test_runner
+        .advance_to_round_at_timestamp(Round::of(1), unlock_time * 1000);
+    let creator_account = utils::create_new_account(&mut env.test_runner);
+    let unauthorised_receipt = utils::lp_lock::withdraw_lp_tokens(
+        &env.owner_badge_address,
+        &lp_lock,
+        &creator_account,
+        &mut env.test_runner,
+    );
+    assert!(
+        unauthorised_receipt.is_commit_failure(),
+        "Only the radix.meme admins should be able to withdraw LP tokens."
+    );
+    utils::lp_lock::withdraw_lp_tokens(
+        &env.owner_badge_address,
+        &lp_lock,
+        &env.owner_account,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    assert!(
+        utils::lp_lock::get_lp_lock_balance(&lp_lock, &mut env.test_runner) == Decimal::ZERO,
+        "All LP tokens should be withdrawn after the unlock time."
+    );
+    assert!(
+        env.test_runner.get_component_balance(
+            env.owner_account.address.clone(),
+            lock_token_state
+                .lp_resource
+                .expect("Time locked LP tokens should have an LP resource.")
+        ) == lp_amount,
+        "Withdrawn LP tokens should be deposited in the admin account."
+    );
+
+    // the LP lock of a token that locks its LP tokens forever cannot be opened, even after the unlock time of other tokens
+    let locked_withdraw_receipt = utils::lp_lock::withdraw_lp_tokens(
+        &env.owner_badge_address,
+        &locked_lp_lock,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    assert!(
+        locked_withdraw_receipt.is_commit_failure(),
+        "LP tokens locked forever should never be withdrawable."
+    );
+    assert!(
+        utils::lp_lock::get_lp_lock_balance(&locked_lp_lock, &mut env.test_runner)
+            == locked_lp_amount,
+        "LP tokens locked forever should stay in the LP lock."
+    );
+}
+
+#[test]
+fn lifecycle_status_tests() {
+    let mut env = utils::setup_test_env(60, false);
+    let token_state = utils::token::get_token_state(&env.token1_component, &mut env.test_runner);
+    assert!(
+        token_state.status == CurveStatus::FairLaunch,
+        "Token with a fair launch period should start in fair launch. {:?}",
+        token_state.status
+    );
+
+    utils::token::change_token_status(
+        "pause",
+        &env.owner_badge_address,
+        &env.token1_component,
+        &env.owner_account,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    let token_state = utils::token::get_token_state(&env.token1_component, &mut env.test_runner);
+    assert!(
+        token_state.status == CurveStatus::Paused,
+        "Token should be paused. {:?}",
+        token_state.status
+    );
+    let paused_buy_receipt = utils::txs::token_buy_with_limit(
+        dec!(100),
+        None,
+        None,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        paused_buy_receipt.is_commit_failure(),
+        "Buys should be rejected while the token is paused."
+    );
+    let creator_account = utils::create_new_account(&mut env.test_runner);
+    let unauthorised_receipt = utils::token::change_token_status(
+        "unpause",
+        &env.owner_badge_address,
+        &env.token1_component,
+        &creator_account,
+        &mut env.test_runner,
+    );
+    assert!(
+        unauthorised_receipt.is_commit_failure(),
+        "Only the radix.meme admins should be able to unpause a token."
+    );
+    utils::token::change_token_status(
+        "unpause",
+        &env.owner_badge_address,
+        &env.token1_component,
+        &env.owner_account,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    let token_state = utils::token::get_token_state(&env.token1_component, &mut env.test_runner);
+    assert!(
+        token_state.status == CurveStatus::FairLaunch,
+        "Token should return to fair launch when unpaused. {:?}",
+        token_state.status
+    );
+
+    // the fair launch ends with the first tx after the fair launch period
+    env.test_runner
+        .advance_to_round_at_timestamp(Round::of(1), (token_state.time_created + 3601) * 1000);
+    let _buy_receipt = utils::txs::token_buy(
+        dec!(100),
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&env.token1_component, &mut env.test_runner);
+    assert!(
+        token_state.status == CurveStatus::Trading,
+        "Token should be trading after the fair launch period. {:?}",
+        token_state.status
+    );
+
+    utils::token::change_token_status(
+        "wind_down",
+        &env.owner_badge_address,
+        &env.token1_component,
+        &env.owner_account,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    let wound_down_buy_receipt = utils::txs::token_buy_with_limit(
+        dec!(100),
+        None,
+        None,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        wound_down_buy_receipt.is_commit_failure(),
+        "Buys should be rejected once the token is wound down."
+    );
+    let token_balance = env.test_runner.get_component_balance(
+        env.owner_account.address.clone(),
+        env.token1_address.clone(),
+    );
+    let _sell_receipt = utils::txs::token_sell(
+        token_balance,
+        &env.token1_address,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&env.token1_component, &mut env.test_runner);
+    assert!(
+        token_state.status == CurveStatus::WoundDown,
+        "Token should stay wound down. {:?}",
+        token_state.status
+    );
+    let unpause_receipt = utils::token::change_token_status(
+        "unpause",
+        &env.owner_badge_address,
+        &env.token1_component,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    assert!(
+        unpause_receipt.is_commit_failure(),
+        "A wound down token cannot be unpaused."
+    );
+}
+
+#[test]
+fn graduated_trade_routing_tests() {
+    let mut env = utils::setup_test_env(0, true);
+    utils::parent::change_default_parameter(
+        "route_graduated_trades",
+        "true",
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    let (token_component, token_address) = utils::token::create_named_token(
+        "Routed Token",
+        "ROUTE",
+        env.token_creation_fee.clone(),
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    assert!(
+        token_state.route_graduated_trades,
+        "Token should route trades after graduation."
+    );
+    utils::load_account_with_xrd(&env.owner_account, dec!("500000"), &mut env.test_runner);
+    let _buy_all_receipt = utils::txs::token_buy(
+        dec!(500000),
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    let pool_component = token_state.pool_address.unwrap();
+    let pool_state = utils::pool::get_pool_state(&pool_component, &mut env.test_runner);
+    let pool_token_reserve = env
+        .test_runner
+        .inspect_vault_balance(pool_state.token_vault.0 .0)
+        .unwrap();
+
+    let token_balance_before = env
+        .test_runner
+        .get_component_balance(env.owner_account.address.clone(), token_address.clone());
+    let routed_buy_quote = utils::txs::token_quote(
+        "quote_buy",
+        dec!(1000),
+        &token_component,
+        &mut env.test_runner,
+    );
+    let _routed_buy_receipt = utils::txs::token_buy(
+        dec!(1000),
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    let tokens_received = env
+        .test_runner
+        .get_component_balance(env.owner_account.address.clone(), token_address.clone())
+        - token_balance_before;
+    let pool_state = utils::pool::get_pool_state(&pool_component, &mut env.test_runner);
+    assert!(
+        tokens_received > Decimal::ZERO
+            && pool_token_reserve
+                - env
+                    .test_runner
+                    .inspect_vault_balance(pool_state.token_vault.0 .0)
+                    .unwrap()
+                == tokens_received,
+        "Routed buy should be filled by the pool. {:?}",
+        tokens_received
+    );
+    assert!(
+        routed_buy_quote.token_amount == tokens_received
+            && routed_buy_quote.fee_amount == dec!(1000) * pool_state.fee_perc,
+        "Routed buy quote should match the pool swap. {:?}",
+        routed_buy_quote
+    );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    assert!(
+        token_state.current_supply == dec!("1000000"),
+        "Routed trades should not change the curve supply. {:?}",
+        token_state.current_supply
+    );
+
+    let xrd_balance_before = env
+        .test_runner
+        .get_component_balance(env.owner_account.address.clone(), XRD);
+    let routed_sell_quote = utils::txs::token_quote(
+        "quote_sell",
+        tokens_received,
+        &token_component,
+        &mut env.test_runner,
+    );
+    let _routed_sell_receipt = utils::txs::token_sell(
+        tokens_received,
+        &token_address,
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    let xrd_received = env
+        .test_runner
+        .get_component_balance(env.owner_account.address.clone(), XRD)
+        - xrd_balance_before;
+    assert!(
+        xrd_received > Decimal::ZERO && xrd_received < dec!(1000),
+        "Routed sell should be filled by the pool. {:?}",
+        xrd_received
+    );
+    assert!(
+        routed_sell_quote.xrd_amount == xrd_received,
+        "Routed sell quote should match the pool swap. {:?}",
+        routed_sell_quote
+    );
+
+    let routed_buy_amount_receipt = utils::txs::token_buy_amount_with_limit(
+        dec!(100),
+        dec!(1000),
+        None,
+        None,
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    routed_buy_amount_receipt.expect_specific_failure(|error| {
+        format!("{:?}", error).contains("Exact amount trades cannot be routed through the pool")
+    });
+    let slippage_receipt = utils::txs::token_buy_with_limit(
+        dec!(1000),
+        Some(dec!(1000000)),
+        None,
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        slippage_receipt.is_commit_failure(),
+        "Slippage limits should apply to routed trades."
+    );
+}
+
+#[test]
+fn fair_launch_clearing_tests() {
+    let mut env = utils::setup_test_env(60, true);
+    let token_state = utils::token::get_token_state(&env.token1_component, &mut env.test_runner);
+    let receipt_address = token_state.fair_launch_receipt_manager.address();
+    let account2 = utils::create_new_account(&mut env.test_runner);
+
+    // buys in different order and sizes during the fair launch only pool XRD
+    for (xrd_amount, account) in [
+        (dec!(1000), &env.owner_account),
+        (dec!(3000), &account2),
+        (dec!(1000), &env.owner_account),
+    ] {
+        utils::txs::token_buy(
+            xrd_amount,
+            account,
+            &env.token1_component,
+            &mut env.test_runner,
+        );
+    }
+    let token_state = utils::token::get_token_state(&env.token1_component, &mut env.test_runner);
+    assert!(
+        token_state.current_supply == Decimal::ZERO,
+        "No tokens should be minted during the fair launch. Supply: {}",
+        token_state.current_supply
+    );
+    let buy_amount_receipt = utils::txs::token_buy_amount_with_limit(
+        dec!(1000),
+        dec!(1000),
+        None,
+        None,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        buy_amount_receipt.is_commit_failure(),
+        "A specific amount of tokens cannot be bought during the fair launch."
+    );
+    let min_tokens_out_receipt = utils::txs::token_buy_with_limit(
+        dec!(1000),
+        Some(dec!(1)),
+        None,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    min_tokens_out_receipt.expect_specific_failure(|error| {
+        format!("{:?}", error)
+            .contains("min_tokens_out cannot be used during the fair launch period")
+    });
+    let early_claim_receipt = utils::txs::token_claim_fair_launch_tokens(
+        &receipt_address,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        early_claim_receipt.is_commit_failure(),
+        "Fair launch tokens cannot be claimed before the fair launch period ends."
+    );
+
+    // the fair launch clears with the first tx after the fair launch period
+    env.test_runner
+        .advance_to_round_at_timestamp(Round::of(1), (token_state.time_created + 3601) * 1000);
+    let fair_launch_xrd = token_state.fair_launch_xrd;
+    let (expected_supply, expected_price) = curve_math::fair_launch_clearing(
+        &token_state.bonding_curve,
+        &CurveState {
+            current_supply: Decimal::ZERO,
+            xrd_reserve: fair_launch_xrd,
+            max_xrd: token_state.max_xrd,
+            max_token_supply_to_trade: token_state.max_token_supply_to_trade,
+            tx_fee_perc: token_state.tx_fee_perc,
+            fee_discount: Decimal::ZERO,
+        },
+    );
+    utils::txs::token_claim_fair_launch_tokens(
+        &receipt_address,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    utils::txs::token_claim_fair_launch_tokens(
+        &receipt_address,
+        &account2,
+        &env.token1_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    let token_state = utils::token::get_token_state(&env.token1_component, &mut env.test_runner);
+    assert!(
+        token_state.status == CurveStatus::Trading
+            && token_state.current_supply == expected_supply
+            && token_state.fair_launch_price == expected_price,
+        "Fair launch should clear at the curve supply for all pooled XRD. Supply: {}, expected: {}, price: {}, expected: {}",
+        token_state.current_supply,
+        expected_supply,
+        token_state.fair_launch_price,
+        expected_price
+    );
+
+    // every receipt is redeemed at the same clearing price
+    let owner_tokens = env.test_runner.get_component_balance(
+        env.owner_account.address.clone(),
+        env.token1_address.clone(),
+    );
+    let account2_tokens = env
+        .test_runner
+        .get_component_balance(account2.address.clone(), env.token1_address.clone());
+    let owner_xrd = fair_launch_xrd * dec!(2) / dec!(5);
+    let account2_xrd = fair_launch_xrd * dec!(3) / dec!(5);
+    for (tokens, xrd) in [(owner_tokens, owner_xrd), (account2_tokens, account2_xrd)] {
+        assert!(
+            (tokens * expected_price - xrd).checked_abs().unwrap() < dec!("0.000001"),
+            "Fair launch receipts should be redeemed at the clearing price. Tokens: {}, XRD: {}, price: {}",
+            tokens,
+            xrd,
+            expected_price
+        );
+    }
+    assert!(
+        owner_tokens + account2_tokens <= expected_supply
+            && expected_supply - owner_tokens - account2_tokens < dec!("0.000001"),
+        "All fair launch tokens should be claimed. Claimed: {}, supply: {}",
+        owner_tokens + account2_tokens,
+        expected_supply
+    );
+
+    // trading continues on the curve from the clearing supply
+    utils::txs::token_sell(
+        owner_tokens,
+        &env.token1_address,
+        &env.owner_account,
+        &env.token1_component,
+        &mut env.test_runner,
+    );
+}
+
+#[test]
+fn fair_launch_cap_tests() {
+    let mut env = utils::setup_test_env(60, false);
+    let badge_address = env
+        .test_runner
+        .create_non_fungible_resource(env.owner_account.address.clone());
+    utils::parent::change_default_fair_launch_cap(
+        Some(dec!(1000)),
+        Some(badge_address.clone()),
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    let (token_component, _token_address) = utils::token::create_named_token(
+        "Capped Token",
+        "CAPPED",
+        env.token_creation_fee.clone(),
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+
+    // a badge is required to buy during the fair launch
+    let no_badge_receipt = utils::txs::token_buy_with_limit(
+        dec!(100),
+        None,
+        None,
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        no_badge_receipt.is_commit_failure(),
+        "Buys without a badge should be rejected during a capped fair launch."
+    );
+
+    // the cap applies to all buys made with the same badge
+    let badge1 = NonFungibleLocalId::integer(1);
+    let badge2 = NonFungibleLocalId::integer(2);
+    utils::txs::token_buy_with_badge(
+        dec!(600),
+        &badge_address,
+        &[badge1.clone()],
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    let over_cap_receipt = utils::txs::token_buy_with_badge(
+        dec!(600),
+        &badge_address,
+        &[badge1.clone()],
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        over_cap_receipt.is_commit_failure(),
+        "Buys with the same badge should not exceed the fair launch cap."
+    );
+    utils::txs::token_buy_with_badge(
+        dec!(400),
+        &badge_address,
+        &[badge1.clone()],
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    utils::txs::token_buy_with_badge(
+        dec!(1000),
+        &badge_address,
+        &[badge2.clone()],
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    let two_badges_receipt = utils::txs::token_buy_with_badge(
+        dec!(100),
+        &badge_address,
+        &[badge1.clone(), badge2.clone()],
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    two_badges_receipt.expect_specific_failure(|error| {
+        format!("{:?}", error)
+            .contains("The fair launch badge proof must contain exactly one badge")
+    });
+
+    // pass holders add a badge proof to buy with a pass during the fair launch
+    let pass_address =
+        env.test_runner
+            .create_fungible_resource(dec!(10), 0, env.owner_account.address.clone());
+    utils::parent::change_pass_fee_discounts(
+        Some(pass_address.clone()),
+        vec![(dec!(1), dec!("0.5"))],
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    let pass_without_badge_receipt = utils::txs::token_buy_with_pass(
+        dec!(500),
+        &pass_address,
+        dec!(1),
+        None,
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    pass_without_badge_receipt.expect_specific_failure(|error| {
+        format!("{:?}", error)
+            .contains("A fair launch badge is required to buy during the fair launch period")
+    });
+    let badge3 = NonFungibleLocalId::integer(3);
+    utils::txs::token_buy_with_pass(
+        dec!(500),
+        &pass_address,
+        dec!(1),
+        Some((&badge_address, &badge3)),
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    assert!(
+        token_state.fair_launch_xrd == dec!(2500),
+        "Fair launch should have pooled the XRD of all badges. XRD: {}",
+        token_state.fair_launch_xrd
+    );
+
+    // the cap no longer applies once the fair launch period has ended
+    env.test_runner
+        .advance_to_round_at_timestamp(Round::of(1), (token_state.time_created + 3601) * 1000);
+    utils::txs::token_buy(
+        dec!(2000),
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+}
+
+#[test]
+fn default_parameter_validation_tests() {
+    let mut env = utils::setup_test_env(60, false);
+    utils::parent::change_default_parameter(
+        "fair_launch_min_xrd",
+        "5000",
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    // the default quadratic curve raises max_xrd_market_cap / 3, so a market cap of 10000 raises less than the fair launch min
+    utils::parent::try_change_default_parameter(
+        "max_xrd_market_cap",
+        "10000",
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    )
+    .expect_specific_failure(|error| {
+        format!("{:?}", error).contains("fair_launch_min_xrd cannot be more than the max XRD")
+    });
+    utils::parent::try_change_default_parameter(
+        "fair_launch_min_xrd",
+        "400000",
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    )
+    .expect_specific_failure(|error| {
+        format!("{:?}", error).contains("fair_launch_min_xrd cannot be more than the max XRD")
+    });
+    utils::parent::try_change_default_parameter(
+        "max_token_supply",
+        "1000000",
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    )
+    .expect_specific_failure(|error| {
+        format!("{:?}", error)
+            .contains("max_token_supply must be more than max_token_supply_to_trade")
+    });
+    utils::parent::change_default_parameter(
+        "max_xrd_market_cap",
+        "30000",
+        &env.owner_badge_address,
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    let parent_state =
+        utils::parent::get_parent_state(&env.parent_component_address, &mut env.test_runner);
+    assert!(
+        parent_state.max_xrd_market_cap == dec!("30000")
+            && parent_state.fair_launch_min_xrd == dec!("5000"),
+        "Valid default parameter changes should be accepted."
+    );
+}
+
+#[test]
+fn fair_launch_refund_tests() {
+    let mut env = utils::setup_test_env(60, false);
+    let (token_component, _token_address) = utils::token::create_soft_cap_token(
+        dec!(5000),
+        &env.owner_badge_address,
+        env.token_creation_fee.clone(),
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    let receipt_address = token_state.fair_launch_receipt_manager.address();
+    let account2 = utils::create_new_account(&mut env.test_runner);
+    for account in [&env.owner_account, &account2] {
+        utils::txs::token_buy(dec!(1000), account, &token_component, &mut env.test_runner);
+    }
+    let early_refund_receipt = utils::txs::token_refund_fair_launch(
+        &receipt_address,
+        &account2,
+        &token_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        early_refund_receipt.is_commit_failure(),
+        "Fair launch receipts cannot be refunded during the fair launch period."
+    );
+
+    // the fair launch fails with the first tx after the fair launch period, as it raised less than the min XRD
+    env.test_runner
+        .advance_to_round_at_timestamp(Round::of(1), (token_state.time_created + 3601) * 1000);
+    let claim_receipt = utils::txs::token_claim_fair_launch_tokens(
+        &receipt_address,
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        claim_receipt.is_commit_failure(),
+        "Fair launch tokens cannot be claimed when the fair launch failed."
+    );
+    let account2_xrd_before = env
+        .test_runner
+        .get_component_balance(account2.address.clone(), XRD);
+    utils::txs::token_refund_fair_launch(
+        &receipt_address,
+        &account2,
+        &token_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    let account2_xrd_after = env
+        .test_runner
+        .get_component_balance(account2.address.clone(), XRD);
+    assert!(
+        account2_xrd_after - account2_xrd_before == dec!(1000),
+        "Fair launch receipts should be refunded for their XRD amount. Refunded: {}",
+        account2_xrd_after - account2_xrd_before
+    );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    assert!(
+        token_state.status == CurveStatus::Refunding && token_state.current_supply == Decimal::ZERO,
+        "Failed fair launch should be refunding without any tokens minted. Status: {:?}, supply: {}",
+        token_state.status,
+        token_state.current_supply
+    );
+    let buy_receipt = utils::txs::token_buy_with_limit(
+        dec!(100),
+        None,
+        None,
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        buy_receipt.is_commit_failure(),
+        "Buys should be rejected after a failed fair launch."
+    );
+    utils::txs::token_refund_fair_launch(
+        &receipt_address,
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    let xrd_vault_balance = env
+        .test_runner
+        .inspect_vault_balance(token_state.xrd_vault.0 .0)
+        .expect("Could not read token curve xrd vault");
+    assert!(
+        xrd_vault_balance == Decimal::ZERO,
+        "All pooled XRD should be refunded. XRD left: {}",
+        xrd_vault_balance
+    );
+}
+
+#[test]
+fn fair_launch_wind_down_refund_tests() {
+    let mut env = utils::setup_test_env(60, false);
+    let (token_component, _token_address) = utils::token::create_soft_cap_token(
+        dec!(5000),
+        &env.owner_badge_address,
+        env.token_creation_fee.clone(),
+        &env.parent_component_address,
+        &env.owner_account,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    let receipt_address = token_state.fair_launch_receipt_manager.address();
+    let account2 = utils::create_new_account(&mut env.test_runner);
+    utils::txs::token_buy(
+        dec!(1000),
+        &account2,
+        &token_component,
+        &mut env.test_runner,
+    );
+
+    // winding down a paused fair launch below the fair launch min XRD fails it, instead of clearing it
+    for method_name in ["pause", "wind_down"] {
+        utils::token::change_token_status(
+            method_name,
+            &env.owner_badge_address,
+            &token_component,
+            &env.owner_account,
+            &mut env.test_runner,
+        )
+        .expect_commit_success();
+    }
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    assert!(
+        token_state.status == CurveStatus::Refunding && token_state.current_supply == Decimal::ZERO,
+        "Fair launch wound down below the min XRD should be refunding without any tokens minted. Status: {:?}, supply: {}",
+        token_state.status,
+        token_state.current_supply
+    );
+    // the refund itself works as in fair_launch_refund_tests
+    utils::txs::token_refund_fair_launch(
+        &receipt_address,
+        &account2,
+        &token_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+}
+
+#[test]
+fn scheduled_start_tests() {
+    let mut env = utils::setup_test_env(60, false);
+    let time_created: i64 = 1_000_000;
+    env.test_runner
+        .advance_to_round_at_timestamp(Round::of(1), time_created * 1000);
+    let start_time = time_created + 3600;
+    let (token_component, _token_dapp_def, token_address) =
+        utils::token::create_token_curve_component_advanced(
+            String::from("Scheduled Token"),
+            String::from("SCHED"),
+            String::from("A token with a scheduled start time"),
+            String::from("https://dexteronradix.com/dexter-logo-and-lettering.svg"),
+            String::from(""),
+            String::from(""),
+            String::from("https://radix.meme"),
+            BondingCurveConfig::Power {
+                exponent: dec!(2),
+                initial_price: Decimal::ZERO,
+            },
+            Some(start_time),
+            env.token_creation_fee.clone(),
+            &env.parent_component_address,
+            &env.owner_account,
+            &mut env.test_runner,
+        );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    assert!(
+        token_state.status == CurveStatus::Scheduled && token_state.start_time == start_time,
+        "Token with a future start time should be scheduled. Status: {:?}, start time: {}",
+        token_state.status,
+        token_state.start_time
+    );
+    let early_buy_receipt = utils::txs::token_buy_with_limit(
+        dec!(100),
+        None,
+        None,
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    assert!(
+        early_buy_receipt.is_commit_failure(),
+        "Buys should be rejected before the start time."
+    );
+
+    // the fair launch is measured from the start time, so it is still running an hour after the token was created
+    env.test_runner
+        .advance_to_round_at_timestamp(Round::of(2), (start_time + 1) * 1000);
+    utils::txs::token_buy(
+        dec!(1000),
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    );
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    assert!(
+        token_state.status == CurveStatus::FairLaunch && token_state.fair_launch_xrd == dec!(1000),
+        "Token should be in its fair launch after the start time. Status: {:?}, fair launch XRD: {}",
+        token_state.status,
+        token_state.fair_launch_xrd
+    );
+
+    env.test_runner
+        .advance_to_round_at_timestamp(Round::of(3), (start_time + 3601) * 1000);
+    utils::txs::token_claim_fair_launch_tokens(
+        &token_state.fair_launch_receipt_manager.address(),
+        &env.owner_account,
+        &token_component,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    let token_state = utils::token::get_token_state(&token_component, &mut env.test_runner);
+    let tokens_claimed = env
+        .test_runner
+        .get_component_balance(env.owner_account.address.clone(), token_address.clone());
+    assert!(
+        token_state.status == CurveStatus::Trading && tokens_claimed == token_state.current_supply,
+        "Fair launch tokens should be claimable once the fair launch period after the start time has ended. Status: {:?}, claimed: {}",
+        token_state.status,
+        tokens_claimed
+    );
+}
+
+#[test]
+fn status_mirror_tests() {
+    let mut env = utils::setup_test_env(60, false);
+    let parent_status = utils::parent::get_token_status(
+        &env.token1_component,
+        &env.parent_component_address,
+        &mut env.test_runner,
+    );
+    assert!(
+        parent_status == CurveStatus::FairLaunch,
+        "Parent should list the token in fair launch. {:?}",
+        parent_status
+    );
+
+    // the end of the fair launch period is time driven, so it is only stored once a tx hits the token curve
+    let token_state = utils::token::get_token_state(&env.token1_component, &mut env.test_runner);
+    env.test_runner
+        .advance_to_round_at_timestamp(Round::of(1), (token_state.time_created + 3601) * 1000);
+    let parent_status = utils::parent::get_token_status(
+        &env.token1_component,
+        &env.parent_component_address,
+        &mut env.test_runner,
+    );
+    assert!(
+        parent_status == CurveStatus::FairLaunch,
+        "Parent status should only change once the status change is stored. {:?}",
+        parent_status
+    );
+
+    utils::token::refresh_status(
+        &env.token1_component,
+        &env.owner_account,
+        &mut env.test_runner,
+    )
+    .expect_commit_success();
+    let token_state = utils::token::get_token_state(&env.token1_component, &mut env.test_runner);
+    assert!(
+        token_state.status == CurveStatus::Trading,
+        "Token should be trading after refreshing its status. {:?}",
+        token_state.status
+    );
+    let parent_status = utils::parent::get_token_status(
+        &env.token1_component,
+        &env.parent_component_address,
+        &mut env.test_runner,
+    );
+    assert!(
+        parent_status == CurveStatus::Trading,
+        "Parent should list the token as trading after refreshing its status. {:?}",
+        parent_status
     );
 }
 
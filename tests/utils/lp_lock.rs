@@ -0,0 +1,50 @@
+use meme_token::radix_meme_lp_lock::radix_meme_lp_lock::RadixMemeLpLock;
+use scrypto_test::prelude::*;
+
+use super::*;
+
+pub fn get_lp_lock_state(
+    lp_lock_component: &ComponentAddress,
+    test_runner: &mut TestRunnerType,
+) -> RadixMemeLpLock {
+    let lp_lock_state = get_component_state::<RadixMemeLpLock, NoExtension, InMemorySubstateDatabase>(
+        lp_lock_component.clone(),
+        test_runner,
+    );
+    lp_lock_state
+}
+
+pub fn get_lp_lock_balance(
+    lp_lock_component: &ComponentAddress,
+    test_runner: &mut TestRunnerType,
+) -> Decimal {
+    let lp_lock_state = get_lp_lock_state(lp_lock_component, test_runner);
+    test_runner
+        .inspect_vault_balance(lp_lock_state.lp_vault.0 .0)
+        .unwrap()
+}
+
+pub fn withdraw_lp_tokens(
+    owner_badge_address: &ResourceAddress,
+    lp_lock_component: &ComponentAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_amount(
+            account.address.clone(),
+            owner_badge_address.clone(),
+            dec!(1),
+        )
+        .call_method(
+            lp_lock_component.clone(),
+            "withdraw_lp_tokens",
+            manifest_args!(),
+        )
+        .try_deposit_entire_worktop_or_abort(account.address, None)
+        .build();
+    test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&account.pubkey)],
+    )
+}
@@ -3,7 +3,9 @@ use scrypto::this_package;
 use scrypto_test::prelude::*;
 use scrypto_unit::*;
 
+pub mod lp_lock;
 pub mod parent;
+pub mod pool;
 pub mod token;
 pub mod txs;
 
@@ -18,6 +20,7 @@ pub struct TestEnv {
     pub owner_badge_address: ResourceAddress,
     pub parent_component_address: ComponentAddress,
     pub parent_dapp_def: ComponentAddress,
+    pub package_address: PackageAddress,
     pub token1_component: ComponentAddress,
     pub token1_address: ResourceAddress,
     pub tx_fee_perc: Decimal,
@@ -42,15 +45,16 @@ pub fn setup_test_env(fair_launch_period: u32, with_fees: bool) -> TestEnv {
         token_creation_fee = dec!("100");
     }
 
-    let (parent_component, parent_dapp_def) = parent::create_parent_component(
+    let (parent_component, parent_dapp_def, package_address) = parent::create_parent_component(
         &owner_badge_address,
-        dec!("1000000"),
+        dec!("2000000"),
         dec!("1000000"),
         dec!("1000000"),
         fair_launch_period,
         tx_fee_perc.clone(),
         listing_fee_perc.clone(),
         creator_fee_perc.clone(),
+        dec!("0.003"),
         token_creation_fee.clone(),
         &owner_account,
         &mut test_runner,
@@ -74,6 +78,7 @@ pub fn setup_test_env(fair_launch_period: u32, with_fees: bool) -> TestEnv {
         owner_badge_address,
         parent_component_address: parent_component,
         parent_dapp_def,
+        package_address,
         token1_component,
         token1_address,
         tx_fee_perc,
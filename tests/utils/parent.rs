@@ -1,4 +1,8 @@
+use meme_token::bonding_curve::BondingCurveConfig;
+use meme_token::curve_math::FeeSchedule;
+use meme_token::dex_adapter::LpTokenPolicy;
 use meme_token::radix_meme_main::test_bindings::RadixMemeMain;
+use meme_token::radix_meme_token_curve::CurveStatus;
 use scrypto_test::prelude::*;
 
 use super::*;
@@ -12,10 +16,11 @@ pub fn create_parent_component(
     tx_fee_perc: Decimal,
     listing_fee_perc: Decimal,
     creator_fee_perc: Decimal,
+    pool_fee_perc: Decimal,
     token_creation_fee: Decimal,
     account: &AccInfo,
     test_runner: &mut TestRunnerType,
-) -> (ComponentAddress, ComponentAddress) {
+) -> (ComponentAddress, ComponentAddress, PackageAddress) {
     let package_address = test_runner.compile_and_publish(this_package!());
     let new_component_manifest = ManifestBuilder::new()
         .call_function(
@@ -33,6 +38,7 @@ pub fn create_parent_component(
                 tx_fee_perc,
                 listing_fee_perc,
                 creator_fee_perc,
+                pool_fee_perc,
                 token_creation_fee,
                 owner_badge_address,
             ],
@@ -57,7 +63,7 @@ pub fn create_parent_component(
     // println!("TokenCurves component address: {:?}", component_address);
     let dapp_def = result.new_component_addresses()[1];
     // println!("TokenCurvese dapp definition address: {:?}", dapp_def);
-    (component_address, dapp_def)
+    (component_address, dapp_def, package_address)
 }
 
 pub fn get_parent_state(
@@ -70,3 +76,449 @@ pub fn get_parent_state(
     );
     pool_state
 }
+
+pub fn change_default_bonding_curve(
+    bonding_curve: BondingCurveConfig,
+    owner_badge_address: &ResourceAddress,
+    parent_address: &ComponentAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_amount(
+            account.address.clone(),
+            owner_badge_address.clone(),
+            dec!(1),
+        )
+        .call_method(
+            parent_address.clone(),
+            "change_default_bonding_curve",
+            manifest_args![bonding_curve],
+        )
+        .build();
+    let receipt = test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&account.pubkey)],
+    );
+
+    if receipt.is_commit_failure() {
+        panic!("Problem with changing default bonding curve! {:?}", receipt);
+    }
+    receipt
+}
+
+pub fn change_default_parameter(
+    param_name: &str,
+    param_value: &str,
+    owner_badge_address: &ResourceAddress,
+    parent_address: &ComponentAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let receipt = try_change_default_parameter(
+        param_name,
+        param_value,
+        owner_badge_address,
+        parent_address,
+        account,
+        test_runner,
+    );
+
+    if receipt.is_commit_failure() {
+        panic!("Problem with changing default parameter! {:?}", receipt);
+    }
+    receipt
+}
+
+pub fn try_change_default_parameter(
+    param_name: &str,
+    param_value: &str,
+    owner_badge_address: &ResourceAddress,
+    parent_address: &ComponentAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_amount(
+            account.address.clone(),
+            owner_badge_address.clone(),
+            dec!(1),
+        )
+        .call_method(
+            parent_address.clone(),
+            "change_default_parameter",
+            manifest_args![param_name.to_string(), param_value.to_string()],
+        )
+        .build();
+    test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&account.pubkey)],
+    )
+}
+
+pub fn sweep_fees(
+    start_index: u64,
+    max_components: u64,
+    owner_badge_address: &ResourceAddress,
+    parent_address: &ComponentAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_amount(
+            account.address.clone(),
+            owner_badge_address.clone(),
+            dec!(1),
+        )
+        .call_method(
+            parent_address.clone(),
+            "sweep_fees",
+            manifest_args![start_index, max_components],
+        )
+        .build();
+    let receipt = test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&account.pubkey)],
+    );
+
+    if receipt.is_commit_failure() {
+        panic!("Problem with sweeping fees! {:?}", receipt);
+    }
+    receipt
+}
+
+pub fn change_default_lp_token_policy(
+    lp_token_policy: LpTokenPolicy,
+    owner_badge_address: &ResourceAddress,
+    parent_address: &ComponentAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_amount(
+            account.address.clone(),
+            owner_badge_address.clone(),
+            dec!(1),
+        )
+        .call_method(
+            parent_address.clone(),
+            "change_default_lp_token_policy",
+            manifest_args![lp_token_policy],
+        )
+        .build();
+    let receipt = test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&account.pubkey)],
+    );
+
+    if receipt.is_commit_failure() {
+        panic!(
+            "Problem with changing default LP token policy! {:?}",
+            receipt
+        );
+    }
+    receipt
+}
+
+pub fn change_default_fee_schedule(
+    fee_schedule: Option<FeeSchedule>,
+    owner_badge_address: &ResourceAddress,
+    parent_address: &ComponentAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_amount(
+            account.address.clone(),
+            owner_badge_address.clone(),
+            dec!(1),
+        )
+        .call_method(
+            parent_address.clone(),
+            "change_default_fee_schedule",
+            manifest_args![fee_schedule],
+        )
+        .build();
+    let receipt = test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&account.pubkey)],
+    );
+
+    if receipt.is_commit_failure() {
+        panic!("Problem with changing default fee schedule! {:?}", receipt);
+    }
+    receipt
+}
+
+pub fn change_pass_fee_discounts(
+    pass_resource: Option<ResourceAddress>,
+    discount_tiers: Vec<(Decimal, Decimal)>,
+    owner_badge_address: &ResourceAddress,
+    parent_address: &ComponentAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_amount(
+            account.address.clone(),
+            owner_badge_address.clone(),
+            dec!(1),
+        )
+        .call_method(
+            parent_address.clone(),
+            "change_pass_fee_discounts",
+            manifest_args![pass_resource, discount_tiers],
+        )
+        .build();
+    let receipt = test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&account.pubkey)],
+    );
+
+    if receipt.is_commit_failure() {
+        panic!("Problem with changing pass fee discounts! {:?}", receipt);
+    }
+    receipt
+}
+
+pub fn change_default_fair_launch_cap(
+    max_xrd: Option<Decimal>,
+    badge_resource: Option<ResourceAddress>,
+    owner_badge_address: &ResourceAddress,
+    parent_address: &ComponentAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_amount(
+            account.address.clone(),
+            owner_badge_address.clone(),
+            dec!(1),
+        )
+        .call_method(
+            parent_address.clone(),
+            "change_default_fair_launch_cap",
+            manifest_args![max_xrd, badge_resource],
+        )
+        .build();
+    let receipt = test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&account.pubkey)],
+    );
+
+    if receipt.is_commit_failure() {
+        panic!("Problem with changing fair launch cap! {:?}", receipt);
+    }
+    receipt
+}
+
+pub fn register_referrer(
+    name: &str,
+    owner_badge_address: &ResourceAddress,
+    parent_address: &ComponentAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_amount(
+            account.address.clone(),
+            owner_badge_address.clone(),
+            dec!(1),
+        )
+        .call_method(
+            parent_address.clone(),
+            "register_referrer",
+            manifest_args![name.to_string()],
+        )
+        .try_deposit_entire_worktop_or_abort(account.address, None)
+        .build();
+    let receipt = test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&account.pubkey)],
+    );
+
+    if receipt.is_commit_failure() {
+        panic!("Problem with registering referrer! {:?}", receipt);
+    }
+    receipt
+}
+
+pub fn deposit_referral_fee(
+    component: &ComponentAddress,
+    referrer: NonFungibleLocalId,
+    xrd_amount: Decimal,
+    parent_address: &ComponentAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let manifest = ManifestBuilder::new()
+        .call_method(
+            account.address.clone(),
+            "withdraw",
+            manifest_args![XRD, xrd_amount],
+        )
+        .take_all_from_worktop(XRD, "fee_bucket")
+        .call_method_with_name_lookup(parent_address.clone(), "deposit_referral_fee", |lookup| {
+            (component.clone(), referrer, lookup.bucket("fee_bucket"))
+        })
+        .try_deposit_entire_worktop_or_abort(account.address, None)
+        .build();
+    test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&account.pubkey)],
+    )
+}
+
+pub fn claim_referral_fees(
+    referrer_badge_address: &ResourceAddress,
+    parent_address: &ComponentAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_amount(
+            account.address.clone(),
+            referrer_badge_address.clone(),
+            dec!(1),
+        )
+        .pop_from_auth_zone("referrer_proof")
+        .call_method_with_name_lookup(parent_address.clone(), "claim_referral_fees", |lookup| {
+            (lookup.proof("referrer_proof"),)
+        })
+        .try_deposit_entire_worktop_or_abort(account.address, None)
+        .build();
+    test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&account.pubkey)],
+    )
+}
+
+pub fn create_mock_dex_adapter(
+    package_address: &PackageAddress,
+    owner_badge_address: &ResourceAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> ComponentAddress {
+    let manifest = ManifestBuilder::new()
+        .call_function(
+            package_address.clone(),
+            "MockDexAdapter",
+            "new",
+            manifest_args!(owner_badge_address.clone()),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&account.pubkey)],
+    );
+
+    if receipt.is_commit_failure() {
+        panic!("Problem with creating mock DEX adapter! {:?}", receipt);
+    }
+    receipt.expect_commit_success().new_component_addresses()[0]
+}
+
+pub fn set_mock_dex_unused_xrd_perc(
+    unused_xrd_perc: Decimal,
+    owner_badge_address: &ResourceAddress,
+    adapter: &ComponentAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_amount(
+            account.address.clone(),
+            owner_badge_address.clone(),
+            dec!(1),
+        )
+        .call_method(
+            adapter.clone(),
+            "set_unused_xrd_perc",
+            manifest_args![unused_xrd_perc],
+        )
+        .build();
+    let receipt = test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&account.pubkey)],
+    );
+
+    if receipt.is_commit_failure() {
+        panic!("Problem with setting mock DEX unused XRD! {:?}", receipt);
+    }
+    receipt
+}
+
+pub fn register_dex_adapter(
+    adapter: &ComponentAddress,
+    name: &str,
+    owner_badge_address: &ResourceAddress,
+    parent_address: &ComponentAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_amount(
+            account.address.clone(),
+            owner_badge_address.clone(),
+            dec!(1),
+        )
+        .call_method(
+            parent_address.clone(),
+            "register_dex_adapter",
+            manifest_args![adapter.clone(), name.to_string()],
+        )
+        .build();
+    let receipt = test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&account.pubkey)],
+    );
+
+    if receipt.is_commit_failure() {
+        panic!("Problem with registering DEX adapter! {:?}", receipt);
+    }
+    receipt
+}
+
+pub fn change_default_dex_adapter(
+    adapter: Option<ComponentAddress>,
+    owner_badge_address: &ResourceAddress,
+    parent_address: &ComponentAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_amount(
+            account.address.clone(),
+            owner_badge_address.clone(),
+            dec!(1),
+        )
+        .call_method(
+            parent_address.clone(),
+            "change_default_dex_adapter",
+            manifest_args![adapter],
+        )
+        .build();
+    test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&account.pubkey)],
+    )
+}
+
+pub fn get_token_status(
+    token_component: &ComponentAddress,
+    parent_address: &ComponentAddress,
+    test_runner: &mut TestRunnerType,
+) -> CurveStatus {
+    let manifest = ManifestBuilder::new()
+        .call_method(
+            parent_address.clone(),
+            "get_token_status",
+            manifest_args!(token_component.clone()),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest_ignoring_fee(manifest, vec![]);
+    if receipt.is_commit_failure() {
+        panic!("Problem with get token status tx! {:?}", receipt);
+    }
+    receipt.expect_commit_success().output(0)
+}
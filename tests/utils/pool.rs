@@ -0,0 +1,97 @@
+use meme_token::radix_meme_pool::radix_meme_pool::RadixMemePool;
+use scrypto_test::prelude::*;
+
+use super::*;
+
+pub fn get_pool_state(
+    pool_component: &ComponentAddress,
+    test_runner: &mut TestRunnerType,
+) -> RadixMemePool {
+    let pool_state = get_component_state::<RadixMemePool, NoExtension, InMemorySubstateDatabase>(
+        pool_component.clone(),
+        test_runner,
+    );
+    pool_state
+}
+
+pub fn pool_swap(
+    input_address: &ResourceAddress,
+    input_amount: Decimal,
+    from_account: &AccInfo,
+    pool_component: &ComponentAddress,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let swap_manifest = ManifestBuilder::new()
+        .call_method(
+            from_account.address.clone(),
+            "withdraw",
+            manifest_args![input_address.clone(), input_amount.clone()],
+        )
+        .take_all_from_worktop(input_address.clone(), "swap_bucket")
+        .call_method_with_name_lookup(pool_component.clone(), "swap", |lookup| {
+            (lookup.bucket("swap_bucket"),)
+        })
+        .try_deposit_entire_worktop_or_abort(from_account.address, None)
+        .build();
+    test_runner.execute_manifest_ignoring_fee(
+        swap_manifest,
+        vec![NonFungibleGlobalId::from_public_key(&from_account.pubkey)],
+    )
+}
+
+pub fn pool_add_liquidity(
+    xrd_amount: Decimal,
+    token_address: &ResourceAddress,
+    token_amount: Decimal,
+    from_account: &AccInfo,
+    pool_component: &ComponentAddress,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let add_liquidity_manifest = ManifestBuilder::new()
+        .call_method(
+            from_account.address.clone(),
+            "withdraw",
+            manifest_args![XRD, xrd_amount.clone()],
+        )
+        .call_method(
+            from_account.address.clone(),
+            "withdraw",
+            manifest_args![token_address.clone(), token_amount.clone()],
+        )
+        .take_all_from_worktop(XRD, "xrd_bucket")
+        .take_all_from_worktop(token_address.clone(), "token_bucket")
+        .call_method_with_name_lookup(pool_component.clone(), "add_liquidity", |lookup| {
+            (lookup.bucket("xrd_bucket"), lookup.bucket("token_bucket"))
+        })
+        .try_deposit_entire_worktop_or_abort(from_account.address, None)
+        .build();
+    test_runner.execute_manifest_ignoring_fee(
+        add_liquidity_manifest,
+        vec![NonFungibleGlobalId::from_public_key(&from_account.pubkey)],
+    )
+}
+
+pub fn pool_remove_liquidity(
+    lp_address: &ResourceAddress,
+    lp_amount: Decimal,
+    from_account: &AccInfo,
+    pool_component: &ComponentAddress,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let remove_liquidity_manifest = ManifestBuilder::new()
+        .call_method(
+            from_account.address.clone(),
+            "withdraw",
+            manifest_args![lp_address.clone(), lp_amount.clone()],
+        )
+        .take_all_from_worktop(lp_address.clone(), "lp_bucket")
+        .call_method_with_name_lookup(pool_component.clone(), "remove_liquidity", |lookup| {
+            (lookup.bucket("lp_bucket"),)
+        })
+        .try_deposit_entire_worktop_or_abort(from_account.address, None)
+        .build();
+    test_runner.execute_manifest_ignoring_fee(
+        remove_liquidity_manifest,
+        vec![NonFungibleGlobalId::from_public_key(&from_account.pubkey)],
+    )
+}
@@ -1,3 +1,4 @@
+use meme_token::bonding_curve::BondingCurveConfig;
 use meme_token::radix_meme_token_curve::radix_meme_token_curve::RadixMemeTokenCurve;
 use scrypto_test::prelude::*;
 
@@ -60,6 +61,115 @@ pub fn create_token_curve_component(
     (component_address, dapp_def, token_address)
 }
 
+// creates a token curve with the default parameters of the parent component, only setting its name and symbol
+pub fn create_named_token(
+    name: &str,
+    symbol: &str,
+    token_creation_fee: Decimal,
+    component_address: &ComponentAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> (ComponentAddress, ResourceAddress) {
+    let (token_component, _dapp_def, token_address) = create_token_curve_component(
+        String::from(name),
+        String::from(symbol),
+        format!("{} on Radix Meme Tokens", name),
+        String::from("https://radix.meme"),
+        String::from(""),
+        String::from(""),
+        String::from(""),
+        token_creation_fee,
+        component_address,
+        account,
+        test_runner,
+    );
+    (token_component, token_address)
+}
+
+// creates a token curve whose fair launch must raise at least fair_launch_min_xrd, by changing the default on the parent component first
+pub fn create_soft_cap_token(
+    fair_launch_min_xrd: Decimal,
+    owner_badge_address: &ResourceAddress,
+    token_creation_fee: Decimal,
+    component_address: &ComponentAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> (ComponentAddress, ResourceAddress) {
+    parent::change_default_parameter(
+        "fair_launch_min_xrd",
+        &fair_launch_min_xrd.to_string(),
+        owner_badge_address,
+        component_address,
+        account,
+        test_runner,
+    );
+    create_named_token(
+        "Soft Cap Token",
+        "SOFT",
+        token_creation_fee,
+        component_address,
+        account,
+        test_runner,
+    )
+}
+
+pub fn create_token_curve_component_advanced(
+    name: String,
+    symbol: String,
+    description: String,
+    icon_url: String,
+    telegram: String,
+    x: String,
+    website: String,
+    bonding_curve: BondingCurveConfig,
+    start_time: Option<i64>,
+    token_creation_fee: Decimal,
+    component_address: &ComponentAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> (ComponentAddress, ComponentAddress, ResourceAddress) {
+    let new_component_manifest = ManifestBuilder::new()
+        .call_method(
+            account.address.clone(),
+            "withdraw",
+            manifest_args![XRD, token_creation_fee.clone()],
+        )
+        .take_all_from_worktop(XRD, "fee_bucket")
+        .call_method_with_name_lookup(
+            component_address.clone(),
+            "new_token_curve_advanced",
+            |lookup| {
+                (
+                    name,
+                    symbol,
+                    description,
+                    icon_url,
+                    telegram,
+                    x,
+                    website,
+                    bonding_curve,
+                    start_time,
+                    lookup.bucket("fee_bucket"),
+                )
+            },
+        )
+        .try_deposit_entire_worktop_or_abort(account.address, None)
+        .build();
+    let receipt = test_runner.execute_manifest_ignoring_fee(
+        new_component_manifest,
+        vec![NonFungibleGlobalId::from_public_key(&account.pubkey)],
+    );
+
+    if receipt.is_commit_failure() {
+        panic!("Problem with creating Token component! {:?}", receipt);
+    }
+    let result = receipt.expect_commit_success();
+    let component_address = result.new_component_addresses()[0];
+    let dapp_def = result.new_component_addresses()[1];
+    let token_address = result.new_resource_addresses()[1];
+    (component_address, dapp_def, token_address)
+}
+
 pub fn get_token_data(token_address: ResourceAddress, test_runner: &mut TestRunnerType) {
     let token_name = test_runner
         .get_metadata(token_address.into(), "name")
@@ -102,8 +212,8 @@ pub fn get_token_state_list(
         format!("{:?}", token_state.max_xrd),
     ));
     result.push((
-        String::from("multiplier"),
-        format!("{:?}", token_state.multiplier),
+        String::from("bonding_curve"),
+        format!("{:?}", token_state.bonding_curve),
     ));
     result.push((
         String::from("last_price"),
@@ -132,3 +242,63 @@ pub fn show_token_state(token_component: &ComponentAddress, test_runner: &mut Te
         println!("{}: {}", field, value);
     }
 }
+
+pub fn claim_creator_fees(
+    token_component: &ComponentAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let token_state = get_token_state(token_component, test_runner);
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_amount(
+            account.address.clone(),
+            token_state.owner_badge_address.clone(),
+            dec!(1),
+        )
+        .call_method(
+            token_component.clone(),
+            "claim_creator_fees",
+            manifest_args!(),
+        )
+        .try_deposit_entire_worktop_or_abort(account.address, None)
+        .build();
+    test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&account.pubkey)],
+    )
+}
+
+pub fn change_token_status(
+    method_name: &str,
+    owner_badge_address: &ResourceAddress,
+    token_component: &ComponentAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_amount(
+            account.address.clone(),
+            owner_badge_address.clone(),
+            dec!(1),
+        )
+        .call_method(token_component.clone(), method_name, manifest_args!())
+        .build();
+    test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&account.pubkey)],
+    )
+}
+
+pub fn refresh_status(
+    token_component: &ComponentAddress,
+    account: &AccInfo,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let manifest = ManifestBuilder::new()
+        .call_method(token_component.clone(), "refresh_status", manifest_args!())
+        .build();
+    test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&account.pubkey)],
+    )
+}
@@ -1,3 +1,4 @@
+use meme_token::radix_meme_token_curve::TradeQuote;
 use scrypto_test::prelude::*;
 
 use super::{AccInfo, TestRunnerType};
@@ -7,6 +8,30 @@ pub fn token_buy(
     from_account: &AccInfo,
     token_curve_address: &ComponentAddress,
     test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let receipt = token_buy_with_limit(
+        xrd_to_send,
+        None,
+        None,
+        from_account,
+        token_curve_address,
+        test_runner,
+    );
+
+    if receipt.is_commit_failure() {
+        panic!("Problem with token buy tx! {:?}", receipt);
+    }
+    // let result = receipt.expect_commit_success();
+    receipt
+}
+
+pub fn token_buy_with_limit(
+    xrd_to_send: Decimal,
+    min_tokens_out: Option<Decimal>,
+    deadline: Option<i64>,
+    from_account: &AccInfo,
+    token_curve_address: &ComponentAddress,
+    test_runner: &mut TestRunnerType,
 ) -> TransactionReceiptV1 {
     let token_buy_manifest = ManifestBuilder::new()
         // .lock_fee(from_account.address.clone(), dec!("10"))
@@ -17,25 +42,190 @@ pub fn token_buy(
         )
         .take_all_from_worktop(XRD, "tx_bucket")
         .call_method_with_name_lookup(token_curve_address.clone(), "buy", |lookup| {
-            (lookup.bucket("tx_bucket"),)
+            (lookup.bucket("tx_bucket"), min_tokens_out, deadline)
+        })
+        .try_deposit_entire_worktop_or_abort(from_account.address, None)
+        .build();
+    test_runner.execute_manifest_ignoring_fee(
+        token_buy_manifest,
+        vec![NonFungibleGlobalId::from_public_key(&from_account.pubkey)],
+    )
+}
+
+pub fn token_buy_with_pass(
+    xrd_to_send: Decimal,
+    pass_address: &ResourceAddress,
+    pass_amount: Decimal,
+    badge: Option<(&ResourceAddress, &NonFungibleLocalId)>,
+    from_account: &AccInfo,
+    token_curve_address: &ComponentAddress,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let mut manifest_builder = ManifestBuilder::new()
+        .create_proof_from_account_of_amount(
+            from_account.address.clone(),
+            pass_address.clone(),
+            pass_amount.clone(),
+        )
+        .create_proof_from_auth_zone_of_amount(pass_address.clone(), pass_amount, "pass_proof");
+    if let Some((badge_address, badge_id)) = badge {
+        manifest_builder = with_badge_proof(
+            manifest_builder,
+            badge_address,
+            &[badge_id.clone()],
+            from_account,
+        );
+    }
+    let token_buy_manifest = manifest_builder
+        .call_method(
+            from_account.address.clone(),
+            "withdraw",
+            manifest_args![XRD, xrd_to_send.clone()],
+        )
+        .take_all_from_worktop(XRD, "tx_bucket")
+        .call_method_with_name_lookup(token_curve_address.clone(), "buy_with_pass", |lookup| {
+            (
+                lookup.proof("pass_proof"),
+                badge.map(|_| lookup.proof("badge_proof")),
+                lookup.bucket("tx_bucket"),
+                None::<Decimal>,
+                None::<i64>,
+            )
+        })
+        .try_deposit_entire_worktop_or_abort(from_account.address, None)
+        .build();
+    test_runner.execute_manifest_ignoring_fee(
+        token_buy_manifest,
+        vec![NonFungibleGlobalId::from_public_key(&from_account.pubkey)],
+    )
+}
+
+pub fn token_buy_with_badge(
+    xrd_to_send: Decimal,
+    badge_address: &ResourceAddress,
+    badge_ids: &[NonFungibleLocalId],
+    from_account: &AccInfo,
+    token_curve_address: &ComponentAddress,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let token_buy_manifest = with_badge_proof(
+        ManifestBuilder::new(),
+        badge_address,
+        badge_ids,
+        from_account,
+    )
+    .call_method(
+        from_account.address.clone(),
+        "withdraw",
+        manifest_args![XRD, xrd_to_send.clone()],
+    )
+    .take_all_from_worktop(XRD, "tx_bucket")
+    .call_method_with_name_lookup(token_curve_address.clone(), "buy_with_badge", |lookup| {
+        (
+            lookup.proof("badge_proof"),
+            lookup.bucket("tx_bucket"),
+            None::<Decimal>,
+            None::<i64>,
+        )
+    })
+    .try_deposit_entire_worktop_or_abort(from_account.address, None)
+    .build();
+    test_runner.execute_manifest_ignoring_fee(
+        token_buy_manifest,
+        vec![NonFungibleGlobalId::from_public_key(&from_account.pubkey)],
+    )
+}
+
+// adds a named proof "badge_proof" of the specified fair launch badges to the manifest
+fn with_badge_proof(
+    manifest_builder: ManifestBuilder,
+    badge_address: &ResourceAddress,
+    badge_ids: &[NonFungibleLocalId],
+    from_account: &AccInfo,
+) -> ManifestBuilder {
+    manifest_builder
+        .create_proof_from_account_of_non_fungibles(
+            from_account.address.clone(),
+            badge_address.clone(),
+            badge_ids.to_vec(),
+        )
+        .create_proof_from_auth_zone_of_non_fungibles(
+            badge_address.clone(),
+            badge_ids.to_vec(),
+            "badge_proof",
+        )
+}
+
+pub fn token_buy_with_referrer(
+    xrd_to_send: Decimal,
+    referrer: NonFungibleLocalId,
+    badge: Option<(&ResourceAddress, &NonFungibleLocalId)>,
+    from_account: &AccInfo,
+    token_curve_address: &ComponentAddress,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let mut manifest_builder = ManifestBuilder::new();
+    if let Some((badge_address, badge_id)) = badge {
+        manifest_builder = with_badge_proof(
+            manifest_builder,
+            badge_address,
+            &[badge_id.clone()],
+            from_account,
+        );
+    }
+    let token_buy_manifest = manifest_builder
+        .call_method(
+            from_account.address.clone(),
+            "withdraw",
+            manifest_args![XRD, xrd_to_send.clone()],
+        )
+        .take_all_from_worktop(XRD, "tx_bucket")
+        .call_method_with_name_lookup(token_curve_address.clone(), "buy_with_referrer", |lookup| {
+            (
+                referrer,
+                badge.map(|_| lookup.proof("badge_proof")),
+                lookup.bucket("tx_bucket"),
+                None::<Decimal>,
+                None::<i64>,
+            )
         })
         .try_deposit_entire_worktop_or_abort(from_account.address, None)
         .build();
-    let receipt = test_runner.execute_manifest_ignoring_fee(
+    test_runner.execute_manifest_ignoring_fee(
         token_buy_manifest,
         vec![NonFungibleGlobalId::from_public_key(&from_account.pubkey)],
+    )
+}
+
+pub fn token_buy_amount(
+    amount_to_buy: Decimal,
+    xrd_to_send: Decimal,
+    from_account: &AccInfo,
+    token_curve_address: &ComponentAddress,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let receipt = token_buy_amount_with_limit(
+        amount_to_buy,
+        xrd_to_send,
+        None,
+        None,
+        from_account,
+        token_curve_address,
+        test_runner,
     );
 
     if receipt.is_commit_failure() {
-        panic!("Problem with token buy tx! {:?}", receipt);
+        panic!("Problem with token buy amount tx! {:?}", receipt);
     }
     // let result = receipt.expect_commit_success();
     receipt
 }
 
-pub fn token_buy_amount(
+pub fn token_buy_amount_with_limit(
     amount_to_buy: Decimal,
     xrd_to_send: Decimal,
+    max_xrd_in: Option<Decimal>,
+    deadline: Option<i64>,
     from_account: &AccInfo,
     token_curve_address: &ComponentAddress,
     test_runner: &mut TestRunnerType,
@@ -49,24 +239,49 @@ pub fn token_buy_amount(
         )
         .take_all_from_worktop(XRD, "tx_bucket")
         .call_method_with_name_lookup(token_curve_address.clone(), "buy_amount", |lookup| {
-            (amount_to_buy, lookup.bucket("tx_bucket"))
+            (
+                amount_to_buy,
+                lookup.bucket("tx_bucket"),
+                max_xrd_in,
+                deadline,
+            )
         })
         .try_deposit_entire_worktop_or_abort(from_account.address, None)
         .build();
-    let receipt = test_runner.execute_manifest_ignoring_fee(
+    test_runner.execute_manifest_ignoring_fee(
         token_buy_manifest,
         vec![NonFungibleGlobalId::from_public_key(&from_account.pubkey)],
+    )
+}
+
+pub fn token_sell(
+    tokens_to_send: Decimal,
+    token_address: &ResourceAddress,
+    from_account: &AccInfo,
+    token_curve_address: &ComponentAddress,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let receipt = token_sell_with_limit(
+        tokens_to_send,
+        None,
+        None,
+        token_address,
+        from_account,
+        token_curve_address,
+        test_runner,
     );
 
     if receipt.is_commit_failure() {
-        panic!("Problem with token buy amount tx! {:?}", receipt);
+        panic!("Problem with token sell tx! {:?}", receipt);
     }
     // let result = receipt.expect_commit_success();
     receipt
 }
 
-pub fn token_sell(
+pub fn token_sell_with_limit(
     tokens_to_send: Decimal,
+    min_xrd_out: Option<Decimal>,
+    deadline: Option<i64>,
     token_address: &ResourceAddress,
     from_account: &AccInfo,
     token_curve_address: &ComponentAddress,
@@ -81,25 +296,47 @@ pub fn token_sell(
         )
         .take_all_from_worktop(token_address.clone(), "tx_bucket")
         .call_method_with_name_lookup(token_curve_address.clone(), "sell", |lookup| {
-            (lookup.bucket("tx_bucket"),)
+            (lookup.bucket("tx_bucket"), min_xrd_out, deadline)
         })
         .try_deposit_entire_worktop_or_abort(from_account.address, None)
         .build();
-    let receipt = test_runner.execute_manifest_ignoring_fee(
+    test_runner.execute_manifest_ignoring_fee(
         token_sell_manifest,
         vec![NonFungibleGlobalId::from_public_key(&from_account.pubkey)],
+    )
+}
+
+pub fn token_sell_for_xrd_amount(
+    xrd_to_receive: Decimal,
+    tokens_to_send: Decimal,
+    token_address: &ResourceAddress,
+    from_account: &AccInfo,
+    token_curve_address: &ComponentAddress,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let receipt = token_sell_for_xrd_amount_with_limit(
+        xrd_to_receive,
+        tokens_to_send,
+        None,
+        None,
+        token_address,
+        from_account,
+        token_curve_address,
+        test_runner,
     );
 
     if receipt.is_commit_failure() {
-        panic!("Problem with token sell tx! {:?}", receipt);
+        panic!("Problem with token sell for xrd tx! {:?}", receipt);
     }
     // let result = receipt.expect_commit_success();
     receipt
 }
 
-pub fn token_sell_for_xrd_amount(
+pub fn token_sell_for_xrd_amount_with_limit(
     xrd_to_receive: Decimal,
     tokens_to_send: Decimal,
+    max_tokens_in: Option<Decimal>,
+    deadline: Option<i64>,
     token_address: &ResourceAddress,
     from_account: &AccInfo,
     token_curve_address: &ComponentAddress,
@@ -116,18 +353,96 @@ pub fn token_sell_for_xrd_amount(
         .call_method_with_name_lookup(
             token_curve_address.clone(),
             "sell_for_xrd_amount",
-            |lookup| (xrd_to_receive, lookup.bucket("tx_bucket")),
+            |lookup| {
+                (
+                    xrd_to_receive,
+                    lookup.bucket("tx_bucket"),
+                    max_tokens_in,
+                    deadline,
+                )
+            },
         )
         .try_deposit_entire_worktop_or_abort(from_account.address, None)
         .build();
-    let receipt = test_runner.execute_manifest_ignoring_fee(
+    test_runner.execute_manifest_ignoring_fee(
         token_sell_for_xrd_manifest,
         vec![NonFungibleGlobalId::from_public_key(&from_account.pubkey)],
-    );
+    )
+}
+
+pub fn token_claim_fair_launch_tokens(
+    receipt_address: &ResourceAddress,
+    from_account: &AccInfo,
+    token_curve_address: &ComponentAddress,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let receipt_amount =
+        test_runner.get_component_balance(from_account.address.clone(), receipt_address.clone());
+    let claim_manifest = ManifestBuilder::new()
+        .call_method(
+            from_account.address.clone(),
+            "withdraw",
+            manifest_args![receipt_address.clone(), receipt_amount],
+        )
+        .take_all_from_worktop(receipt_address.clone(), "receipts_bucket")
+        .call_method_with_name_lookup(
+            token_curve_address.clone(),
+            "claim_fair_launch_tokens",
+            |lookup| (lookup.bucket("receipts_bucket"),),
+        )
+        .try_deposit_entire_worktop_or_abort(from_account.address, None)
+        .build();
+    test_runner.execute_manifest_ignoring_fee(
+        claim_manifest,
+        vec![NonFungibleGlobalId::from_public_key(&from_account.pubkey)],
+    )
+}
+
+pub fn token_refund_fair_launch(
+    receipt_address: &ResourceAddress,
+    from_account: &AccInfo,
+    token_curve_address: &ComponentAddress,
+    test_runner: &mut TestRunnerType,
+) -> TransactionReceiptV1 {
+    let receipt_amount =
+        test_runner.get_component_balance(from_account.address.clone(), receipt_address.clone());
+    let refund_manifest = ManifestBuilder::new()
+        .call_method(
+            from_account.address.clone(),
+            "withdraw",
+            manifest_args![receipt_address.clone(), receipt_amount],
+        )
+        .take_all_from_worktop(receipt_address.clone(), "receipts_bucket")
+        .call_method_with_name_lookup(
+            token_curve_address.clone(),
+            "refund_fair_launch",
+            |lookup| (lookup.bucket("receipts_bucket"),),
+        )
+        .try_deposit_entire_worktop_or_abort(from_account.address, None)
+        .build();
+    test_runner.execute_manifest_ignoring_fee(
+        refund_manifest,
+        vec![NonFungibleGlobalId::from_public_key(&from_account.pubkey)],
+    )
+}
+
+pub fn token_quote(
+    method_name: &str,
+    amount: Decimal,
+    token_curve_address: &ComponentAddress,
+    test_runner: &mut TestRunnerType,
+) -> TradeQuote {
+    let token_quote_manifest = ManifestBuilder::new()
+        .call_method(
+            token_curve_address.clone(),
+            method_name,
+            manifest_args![amount],
+        )
+        .build();
+    let receipt = test_runner.execute_manifest_ignoring_fee(token_quote_manifest, vec![]);
 
     if receipt.is_commit_failure() {
-        panic!("Problem with token sell for xrd tx! {:?}", receipt);
+        panic!("Problem with token quote tx! {:?}", receipt);
     }
-    // let result = receipt.expect_commit_success();
-    receipt
+    receipt.expect_commit_success().output(0)
 }